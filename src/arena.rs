@@ -0,0 +1,290 @@
+//! An arena-backed tree, storing every node in a flat `Vec` and linking them
+//! by `NodeId` handles, so moving to a parent, child or sibling takes constant time.
+
+use alloc::{vec, vec::Vec};
+
+use super::tree::Tree;
+
+/// A handle to a node within an `ArenaTree`.
+/// Handles of removed nodes may be reused by nodes added later.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NodeId(usize);
+
+impl NodeId {
+    /// Returns the index of the node within the arena.
+    pub fn index(self) -> usize { self.0 }
+}
+
+#[derive(Debug, Clone)]
+struct Node<T> {
+    label: T,
+    parent: Option<NodeId>,
+    first_child: Option<NodeId>,
+    last_child: Option<NodeId>,
+    prev_sibling: Option<NodeId>,
+    next_sibling: Option<NodeId>
+}
+
+impl<T> Node<T> {
+    fn new(label: T, parent: Option<NodeId>) -> Self {
+        Self {
+            label,
+            parent,
+            first_child: None,
+            last_child: None,
+            prev_sibling: None,
+            next_sibling: None
+        }
+    }
+}
+
+/// A tree stored in a flat arena of nodes.
+/// # Example
+/// ```
+/// use tt::arena::ArenaTree;
+///
+/// let mut tree = ArenaTree::new("root");
+/// let root = tree.root();
+/// let a = tree.append_child(root, "a").unwrap();
+/// let b = tree.append_child(root, "b").unwrap();
+/// let c = tree.append_child(a, "c").unwrap();
+///
+/// assert_eq!(tree.parent(c), Some(a));
+/// assert_eq!(tree.next_sibling(a), Some(b));
+/// assert_eq!(tree.prev_sibling(b), Some(a));
+/// assert_eq!(tree.children(root).collect::<Vec<_>>(), vec![a, b]);
+/// assert_eq!(tree.label(c), Some(&"c"));
+///
+/// assert_eq!(tree.remove(a), Some("a"));
+/// assert!(!tree.contains(c));
+/// assert_eq!(tree.size(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ArenaTree<T> {
+    nodes: Vec<Option<Node<T>>>,
+    free: Vec<usize>,
+    root: NodeId
+}
+
+impl<T> ArenaTree<T> {
+    /// Constructs and returns a new `ArenaTree` holding only a root with `label`.
+    pub fn new(label: T) -> Self {
+        Self {
+            nodes: vec![Some(Node::new(label, None))],
+            free: Vec::new(),
+            root: NodeId(0)
+        }
+    }
+
+    /// Returns the handle of the root.
+    pub fn root(&self) -> NodeId { self.root }
+    /// Returns the number of nodes in the tree, including the root.
+    pub fn size(&self) -> usize { self.nodes.len() - self.free.len() }
+    /// Returns whether `id` points to a node of the tree.
+    pub fn contains(&self, id: NodeId) -> bool { self.node(id).is_some() }
+
+    pub fn label(&self, id: NodeId) -> Option<&T> { self.node(id).map(|n| &n.label) }
+    pub fn label_mut(&mut self, id: NodeId) -> Option<&mut T> { self.node_mut(id).map(|n| &mut n.label) }
+
+    pub fn parent(&self, id: NodeId) -> Option<NodeId> { self.node(id)?.parent }
+    pub fn first_child(&self, id: NodeId) -> Option<NodeId> { self.node(id)?.first_child }
+    pub fn last_child(&self, id: NodeId) -> Option<NodeId> { self.node(id)?.last_child }
+    pub fn prev_sibling(&self, id: NodeId) -> Option<NodeId> { self.node(id)?.prev_sibling }
+    pub fn next_sibling(&self, id: NodeId) -> Option<NodeId> { self.node(id)?.next_sibling }
+
+    /// Returns an iterator over the handles of the children of `id`, in order.
+    pub fn children(&self, id: NodeId) -> Children<'_, T> {
+        Children { tree: self, next: self.first_child(id) }
+    }
+
+    /// Returns an iterator over the handles of the ancestors of `id`,
+    /// from its parent up to the root.
+    /// # Example
+    /// ```
+    /// use tt::arena::ArenaTree;
+    ///
+    /// let mut tree = ArenaTree::new(0);
+    /// let root = tree.root();
+    /// let a = tree.append_child(root, 1).unwrap();
+    /// let b = tree.append_child(a, 2).unwrap();
+    ///
+    /// assert_eq!(tree.ancestors(b).collect::<Vec<_>>(), vec![a, root]);
+    /// assert_eq!(tree.ancestors(root).count(), 0);
+    /// ```
+    pub fn ancestors(&self, id: NodeId) -> Ancestors<'_, T> {
+        Ancestors { tree: self, next: self.parent(id) }
+    }
+
+    /// Returns the amount of ancestors of `id`, the root having depth `0`.
+    /// Returns none if `id` isn't a node of the tree.
+    pub fn depth(&self, id: NodeId) -> Option<usize> {
+        self.node(id)?;
+        Some(self.ancestors(id).count())
+    }
+
+    /// Appends a new node with `label` as the last child of `parent`, returning its handle.
+    /// Returns none, dropping `label`, if `parent` isn't a node of the tree.
+    pub fn append_child(&mut self, parent: NodeId, label: T) -> Option<NodeId> {
+        let prev = self.node(parent)?.last_child;
+        let mut node = Node::new(label, Some(parent));
+        node.prev_sibling = prev;
+
+        let id = match self.free.pop() {
+            Some(i) => {
+                self.nodes[i] = Some(node);
+                NodeId(i)
+            }
+            None => {
+                self.nodes.push(Some(node));
+                NodeId(self.nodes.len() - 1)
+            }
+        };
+
+        match prev {
+            Some(prev) => self.node_mut(prev)?.next_sibling = Some(id),
+            None => self.node_mut(parent)?.first_child = Some(id)
+        }
+        self.node_mut(parent)?.last_child = Some(id);
+        Some(id)
+    }
+
+    /// Removes the node `id` along with all of its descendants, returning its label.
+    /// Returns none if `id` is the root or isn't a node of the tree.
+    pub fn remove(&mut self, id: NodeId) -> Option<T> {
+        if id == self.root || !self.contains(id) {
+            return None;
+        }
+
+        self.unlink(id);
+        let node = self.nodes[id.0].take()?;
+        self.free.push(id.0);
+
+        let mut stack: Vec<NodeId> = node.first_child.into_iter().collect();
+        while let Some(child) = stack.pop() {
+            if let Some(removed) = self.nodes[child.0].take() {
+                self.free.push(child.0);
+                stack.extend(removed.next_sibling);
+                stack.extend(removed.first_child);
+            }
+        }
+
+        Some(node.label)
+    }
+
+    fn unlink(&mut self, id: NodeId) {
+        let (parent, prev, next) = match self.node(id) {
+            Some(node) => (node.parent, node.prev_sibling, node.next_sibling),
+            None => return
+        };
+
+        match prev.and_then(|prev| self.node_mut(prev)) {
+            Some(prev) => prev.next_sibling = next,
+            None => if let Some(parent) = parent.and_then(|p| self.node_mut(p)) { parent.first_child = next }
+        }
+        match next.and_then(|next| self.node_mut(next)) {
+            Some(next) => next.prev_sibling = prev,
+            None => if let Some(parent) = parent.and_then(|p| self.node_mut(p)) { parent.last_child = prev }
+        }
+    }
+
+    fn node(&self, id: NodeId) -> Option<&Node<T>> {
+        self.nodes.get(id.0)?.as_ref()
+    }
+
+    fn node_mut(&mut self, id: NodeId) -> Option<&mut Node<T>> {
+        self.nodes.get_mut(id.0)?.as_mut()
+    }
+}
+
+impl<T> From<Tree<T>> for ArenaTree<T> {
+    /// Moves every node of `tree` into an arena.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    /// use tt::arena::ArenaTree;
+    ///
+    /// let mut tree = Tree::new(0);
+    /// tree.create_subtree(1);
+    /// tree.create_subtree(2);
+    ///
+    /// let arena = ArenaTree::from(tree.clone());
+    /// assert_eq!(arena.size(), 3);
+    /// assert_eq!(Tree::from(arena), tree);
+    /// ```
+    fn from(tree: Tree<T>) -> Self {
+        let (label, children) = tree.into_parts();
+        let mut arena = ArenaTree::new(label);
+
+        let root = arena.root;
+        let mut stack: Vec<_> = children.into_iter().rev().map(|c| (c, root)).collect();
+        while let Some((tree, parent)) = stack.pop() {
+            let (label, children) = tree.into_parts();
+            if let Some(id) = arena.append_child(parent, label) {
+                stack.extend(children.into_iter().rev().map(|c| (c, id)));
+            }
+        }
+
+        arena
+    }
+}
+
+impl<T> From<ArenaTree<T>> for Tree<T> {
+    /// Moves every node of `arena` reachable from its root into a `Tree`.
+    fn from(mut arena: ArenaTree<T>) -> Self {
+        let root = arena.root;
+        let mut take = |id: NodeId| arena.nodes[id.0].take().map(|n| (n.label, n.first_child, n.next_sibling));
+
+        let (label, first, _) = take(root).expect("the root of an arena is always present");
+        let mut stack = vec![(Tree::new(label), first)];
+        loop {
+            let next = stack.last_mut().and_then(|(_, next)| next.take());
+            match next.and_then(&mut take) {
+                Some((label, first, next)) => {
+                    if let Some((_, sibling)) = stack.last_mut() {
+                        *sibling = next;
+                    }
+                    stack.push((Tree::new(label), first));
+                }
+                None => {
+                    let (tree, _) = stack.pop().expect("the stack holds at least the root");
+                    match stack.last_mut() {
+                        Some((parent, _)) => parent.attach_child(tree),
+                        None => return tree
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// An iterator over the children of a node in an `ArenaTree`.
+pub struct Children<'a, T> {
+    tree: &'a ArenaTree<T>,
+    next: Option<NodeId>
+}
+
+impl<'a, T> Iterator for Children<'a, T> {
+    type Item = NodeId;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let id = self.next?;
+        self.next = self.tree.next_sibling(id);
+        Some(id)
+    }
+}
+
+/// An iterator over the ancestors of a node in an `ArenaTree`.
+pub struct Ancestors<'a, T> {
+    tree: &'a ArenaTree<T>,
+    next: Option<NodeId>
+}
+
+impl<'a, T> Iterator for Ancestors<'a, T> {
+    type Item = NodeId;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let id = self.next?;
+        self.next = self.tree.parent(id);
+        Some(id)
+    }
+}
@@ -0,0 +1,221 @@
+//! `tt` - reads a tree from a file or the standard input, and prints it or an edited copy of it.
+//!
+//! ```text
+//! tt [-i FILE] [-f FORMAT] [-t FORMAT] COMMAND [ARGS]
+//! ```
+//!
+//! Commands:
+//! * `print` - Draws the tree
+//! * `get <path>` - Writes the subtree at `path`
+//! * `add <path> <label>` - Appends a new leaf with `label` to the subtree at `path`, writing the tree
+//! * `rm <path>` - Removes the subtree at `path`, writing the tree
+//! * `fmt` - Writes the tree
+//! * `edit` - Edits the tree read from `-i` interactively, see below
+//!
+//! Paths are `/`-separated child indices, with `/` alone being the root. Trees are written
+//! in the format given by `-t`, defaulting to the one they are read in, given by `-f`.
+//! Formats are `sexpr` (the default) and `outline`, indented by four spaces.
+//! JSON isn't supported: reading and writing it as text needs `serde_json`, which isn't a dependency.
+//!
+//! Editing is line based rather than a full-screen terminal UI: keys aren't read raw, and nothing
+//! happens until a line is entered. While editing, the tree is drawn after every line read from
+//! the standard input, which holds either a command of `tt::script`, a movement command of
+//! `tt::command`, or one of:
+//! * `:w` - Saves the tree to the input file, in the output format
+//! * `:u` / `:r` - Undoes / redoes the last edit
+//! * `:q` - Quits, without saving
+
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io::{self, BufRead, Read, Write};
+use std::process;
+
+use tt::focus::Focus;
+use tt::printer::TreePrinter;
+use tt::script;
+use tt::tree::{Path, Tree};
+
+const USAGE: &str = "usage: tt [-i FILE] [-f FORMAT] [-t FORMAT] (print | get <path> | add <path> <label> | rm <path> | fmt | edit)";
+
+#[derive(Clone, Copy)]
+enum Format {
+    Sexpr,
+    Outline
+}
+
+impl Format {
+    fn parse(s: &str) -> Result<Self, Error> {
+        match s {
+            "sexpr"   => Ok(Format::Sexpr),
+            "outline" => Ok(Format::Outline),
+            _ => Err(Error::Usage(format!("unknown format `{}`", s)))
+        }
+    }
+
+    fn read(self, s: &str) -> Result<Tree<String>, Error> {
+        let tree = match self {
+            Format::Sexpr   => Tree::from_sexpr(s),
+            Format::Outline => Tree::from_outline(s, "    ")
+        };
+        tree.map_err(|e| Error::Failed(e.to_string()))
+    }
+
+    fn write(self, tree: &Tree<String>) -> String {
+        match self {
+            Format::Sexpr   => tree.to_sexpr() + "\n",
+            Format::Outline => tree.to_outline("    ")
+        }
+    }
+}
+
+enum Error {
+    Usage(String),
+    Failed(String)
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Usage(message)  => write!(f, "{}\n{}", message, USAGE),
+            Error::Failed(message) => write!(f, "{}", message)
+        }
+    }
+}
+
+fn main() {
+    if let Err(e) = run(env::args().skip(1).collect()) {
+        eprintln!("tt: {}", e);
+        process::exit(match e {
+            Error::Usage(_)  => 2,
+            Error::Failed(_) => 1
+        });
+    }
+}
+
+fn run(args: Vec<String>) -> Result<(), Error> {
+    let mut args = args.into_iter();
+    let mut input = None;
+    let mut from = Format::Sexpr;
+    let mut to = None;
+
+    let command = loop {
+        let arg = args.next().ok_or_else(|| Error::Usage(String::from("missing command")))?;
+        let mut value = || args.next().ok_or_else(|| Error::Usage(format!("missing value for `{}`", arg)));
+        match arg.as_str() {
+            "-i" | "--input" => input = Some(value()?),
+            "-f" | "--from"  => from = Format::parse(&value()?)?,
+            "-t" | "--to"    => to = Some(Format::parse(&value()?)?),
+            "-h" | "--help"  => {
+                println!("{}", USAGE);
+                return Ok(());
+            }
+            _ => break arg
+        }
+    };
+    let to = to.unwrap_or(from);
+    let operands: Vec<String> = args.collect();
+    match (command.as_str(), operands.len()) {
+        ("print", 0) | ("get", 1) | ("add", 2) | ("rm", 1) | ("fmt", 0) | ("edit", 0) => {}
+        ("print", _) | ("get", _) | ("add", _) | ("rm", _) | ("fmt", _) | ("edit", _) => {
+            return Err(Error::Usage(format!("wrong number of arguments for `{}`", command)))
+        }
+        _ => return Err(Error::Usage(format!("unknown command `{}`", command)))
+    }
+
+    let text = match &input {
+        Some(file) => fs::read_to_string(file).map_err(|e| Error::Failed(format!("{}: {}", file, e)))?,
+        None => {
+            let mut text = String::new();
+            io::stdin().read_to_string(&mut text).map_err(|e| Error::Failed(e.to_string()))?;
+            text
+        }
+    };
+    let mut tree = from.read(&text)?;
+    if command == "edit" {
+        let file = input.ok_or_else(|| Error::Usage(String::from("`edit` needs an input file")))?;
+        return edit(tree, &file, to);
+    }
+
+    let output = match (command.as_str(), operands.as_slice()) {
+        ("print", []) => TreePrinter::new().print(&tree) + "\n",
+        ("get", [at]) => to.write(subtree(&mut tree, &path(at)?)?),
+        ("add", [at, label]) => {
+            subtree(&mut tree, &path(at)?)?.create_subtree(label.clone());
+            to.write(&tree)
+        }
+        ("rm", [at]) => {
+            let at = path(at)?;
+            let (&i, parent) = at.split_last().ok_or_else(|| Error::Failed(String::from("can't remove the root")))?;
+            subtree(&mut tree, parent)?.remove_child(i).ok_or_else(|| no_subtree(&at))?;
+            to.write(&tree)
+        }
+        ("fmt", []) => to.write(&tree),
+        _ => unreachable!()
+    };
+    print!("{}", output);
+    Ok(())
+}
+
+/// Runs an interactive editing session on `tree`, saving it to `file` in `format`.
+fn edit(tree: Tree<String>, file: &str, format: Format) -> Result<(), Error> {
+    let mut focus = Focus::from(tree, None).expect("the root always exists");
+    focus.set_journal(true);
+    let mut status = String::new();
+    let mut lines = io::stdin().lock().lines();
+
+    loop {
+        draw(&focus, &status);
+        let line = match lines.next() {
+            Some(line) => line.map_err(|e| Error::Failed(e.to_string()))?,
+            None => return Ok(())
+        };
+
+        status = match line.trim() {
+            ":q" => return Ok(()),
+            ":w" => match fs::write(file, format.write(focus.tree())) {
+                Ok(()) => format!("saved {}", file),
+                Err(e) => format!("{}: {}", file, e)
+            },
+            ":u" => String::from(if focus.undo() { "undone" } else { "nothing to undo" }),
+            ":r" => String::from(if focus.redo() { "redone" } else { "nothing to redo" }),
+            line => match script::run(&mut focus, line) {
+                Err(script::ScriptError::Parse(e)) => match focus.apply_str(line) {
+                    Err(_) => e.message().to_string(),
+                    Ok(()) => String::new()
+                },
+                Err(e) => e.to_string(),
+                Ok(()) => String::new()
+            }
+        };
+    }
+}
+
+/// Draws the tree of `focus`, marking the focused subtree, followed by `status` and a prompt.
+fn draw(focus: &Focus<String>, status: &str) {
+    let drawing = TreePrinter::new().print(focus.tree());
+    let mut out = io::stdout().lock();
+    for ((path, _), line) in focus.tree().iter_paths().zip(drawing.lines()) {
+        let marker = if &path == focus.path() { '>' } else { ' ' };
+        let _ = writeln!(out, "{} {}", marker, line);
+    }
+    let _ = write!(out, "{}\n: ", status);
+    let _ = out.flush();
+}
+
+/// Parses a path of `/`-separated child indices.
+fn path(s: &str) -> Result<Path, Error> {
+    s.split('/')
+        .filter(|step| !step.is_empty())
+        .map(|step| step.parse().map_err(|_| Error::Usage(format!("invalid path `{}`", s))))
+        .collect()
+}
+
+fn subtree<'a>(tree: &'a mut Tree<String>, at: &[usize]) -> Result<&'a mut Tree<String>, Error> {
+    tree.get_mut(at).ok_or_else(|| no_subtree(at))
+}
+
+fn no_subtree(at: &[usize]) -> Error {
+    let steps: Vec<String> = at.iter().map(usize::to_string).collect();
+    Error::Failed(format!("no subtree at `/{}`", steps.join("/")))
+}
@@ -0,0 +1,129 @@
+//! Labels borrowing from the input they are deserialized from.
+//!
+//! `Tree<&'de str>` already deserializes without allocating labels, but fails on
+//! strings the format can't lend out, such as JSON strings with escapes. `Tree<CowStr<'de>>`
+//! borrows whenever possible and falls back to an owned string otherwise.
+//! # Example
+//! ```
+//! use serde::Deserialize;
+//! use tt::tree::Tree;
+//! use tt::borrowed::CowStr;
+//!
+//! #[derive(Deserialize)]
+//! struct Document<'a> {
+//!     #[serde(borrow)]
+//!     outline: Tree<CowStr<'a>>
+//! }
+//! ```
+
+use alloc::string::{String, ToString};
+use alloc::borrow::Cow;
+use core::borrow::Borrow;
+use core::fmt;
+use core::ops::Deref;
+
+use serde::de::{self, Deserializer, Visitor};
+use serde::{Serialize, Serializer, Deserialize};
+
+use super::tree::Tree;
+
+/// A string label borrowed from the input whenever possible.
+/// # Example
+/// ```
+/// use std::borrow::Cow;
+/// use serde::Deserialize;
+/// use serde::de::value::{BorrowedStrDeserializer, StrDeserializer, Error};
+/// use tt::borrowed::CowStr;
+///
+/// let input = String::from("label");
+/// let borrowed = CowStr::deserialize(BorrowedStrDeserializer::<Error>::new(&input)).unwrap();
+/// assert!(matches!(borrowed.0, Cow::Borrowed(_)));
+///
+/// let owned = CowStr::deserialize(StrDeserializer::<Error>::new(&input)).unwrap();
+/// assert!(matches!(owned.0, Cow::Owned(_)));
+/// assert_eq!(borrowed, owned);
+/// assert_eq!(&*owned, "label");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct CowStr<'a>(pub Cow<'a, str>);
+
+impl<'a> CowStr<'a> {
+    /// Returns the label as an owned string, allocating only if it is borrowed.
+    pub fn into_owned(self) -> String { self.0.into_owned() }
+}
+
+impl<'a> Deref for CowStr<'a> {
+    type Target = str;
+
+    fn deref(&self) -> &str { &self.0 }
+}
+
+impl<'a> Borrow<str> for CowStr<'a> {
+    fn borrow(&self) -> &str { &self.0 }
+}
+
+impl<'a> fmt::Display for CowStr<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl<'a> From<&'a str> for CowStr<'a> {
+    fn from(s: &'a str) -> Self { Self(Cow::Borrowed(s)) }
+}
+
+impl<'a> From<String> for CowStr<'a> {
+    fn from(s: String) -> Self { Self(Cow::Owned(s)) }
+}
+
+impl<'a> From<CowStr<'a>> for Cow<'a, str> {
+    fn from(s: CowStr<'a>) -> Self { s.0 }
+}
+
+impl<'a> Serialize for CowStr<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for CowStr<'a> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(CowStrVisitor)
+    }
+}
+
+struct CowStrVisitor;
+
+impl<'de> Visitor<'de> for CowStrVisitor {
+    type Value = CowStr<'de>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a string")
+    }
+
+    fn visit_borrowed_str<E: de::Error>(self, v: &'de str) -> Result<Self::Value, E> {
+        Ok(CowStr(Cow::Borrowed(v)))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(CowStr(Cow::Owned(v.to_string())))
+    }
+
+    fn visit_string<E: de::Error>(self, v: String) -> Result<Self::Value, E> {
+        Ok(CowStr(Cow::Owned(v)))
+    }
+}
+
+impl<'a> Tree<CowStr<'a>> {
+    /// Consumes the tree, returning a tree of `Cow` labels without copying any string.
+    pub fn into_cow(self) -> Tree<Cow<'a, str>> { self.map(Cow::from) }
+
+    /// Consumes the tree, returning a tree of owned labels.
+    pub fn into_owned(self) -> Tree<String> { self.map(CowStr::into_owned) }
+}
+
+/// Deserializes a `Tree<Cow<'de, str>>`, borrowing labels whenever possible, for use with
+/// `#[serde(borrow, deserialize_with = "tt::borrowed::deserialize_cow")]`.
+pub fn deserialize_cow<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Tree<Cow<'de, str>>, D::Error> {
+    Tree::<CowStr<'de>>::deserialize(deserializer).map(Tree::into_cow)
+}
@@ -0,0 +1,197 @@
+//! Movement commands, terse strings of keys and paths standing for sequences of jumps,
+//! for driving a `Focus` from the keyboard.
+//!
+//! Keys, each optionally preceded by a count, may be separated by whitespace:
+//! * `u` - `Jump::Up`
+//! * `d` / `D` - `Jump::Down` / `Jump::DownLast`
+//! * `h` / `l` - `Jump::Lateral(-1)` / `Jump::Lateral(1)`, the count setting the distance
+//! * `H` / `L` - `Jump::FirstSibling` / `Jump::LastSibling`
+//! * `r` - `Jump::Root`
+//! * `n` / `p` - `Jump::Next` / `Jump::Prev`
+//! * `N` / `P` - `Jump::NextLeaf` / `Jump::PrevLeaf`
+//!
+//! Any other count repeats its key. Paths are `/`-separated steps, `..` going up,
+//! `.` staying and an index going to that child; a path starting with `/` is absolute.
+//! # Example
+//! ```
+//! use tt::focus::Jump;
+//! use tt::command;
+//!
+//! assert_eq!(command::parse("uu3l d").unwrap(), vec![Jump::Up, Jump::Up, Jump::Lateral(3), Jump::Down]);
+//! assert_eq!(command::parse("../2/0").unwrap(), vec![Jump::Up, Jump::Child(2), Jump::Child(0)]);
+//! assert_eq!(command::parse("/1/0 2n").unwrap(), vec![Jump::ToPath(vec![1, 0]), Jump::Next, Jump::Next]);
+//! assert!(command::parse("3").is_err());
+//! ```
+
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::error::Error;
+use core::fmt;
+
+use super::focus::{Focus, Jump, JumpError};
+use super::format::{Cursor, ParseError};
+
+/// The reason a command couldn't be applied.
+/// * `CommandError::Parse(e)` - The command isn't well-formed.
+/// * `CommandError::Jump(e)` - One of its jumps is impossible.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommandError {
+    Parse(ParseError),
+    Jump(JumpError)
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CommandError::Parse(e) => e.fmt(f),
+            CommandError::Jump(e)  => e.fmt(f)
+        }
+    }
+}
+
+impl Error for CommandError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            CommandError::Parse(e) => Some(e),
+            CommandError::Jump(e)  => Some(e)
+        }
+    }
+}
+
+impl From<ParseError> for CommandError {
+    fn from(e: ParseError) -> Self { CommandError::Parse(e) }
+}
+
+impl From<JumpError> for CommandError {
+    fn from(e: JumpError) -> Self { CommandError::Jump(e) }
+}
+
+/// Parses a movement command into the jumps it stands for.
+pub fn parse(s: &str) -> Result<Vec<Jump>, ParseError> {
+    let mut cursor = Cursor::new(s);
+    let mut jumps = Vec::new();
+    loop {
+        let (line, column) = cursor.position();
+        let count = match cursor.peek() {
+            None => return Ok(jumps),
+            Some('/') => {
+                absolute(&mut cursor, &mut jumps)?;
+                continue;
+            }
+            Some('.') => {
+                step(&mut cursor, &mut jumps)?;
+                steps(&mut cursor, &mut jumps)?;
+                continue;
+            }
+            Some(c) if c.is_ascii_digit() => Some(number(&mut cursor)?),
+            Some(_) => None
+        };
+
+        if let (Some(i), Some('/')) = (count, cursor.peek()) {
+            jumps.push(Jump::Child(i));
+            steps(&mut cursor, &mut jumps)?;
+            continue;
+        }
+
+        let n = count.unwrap_or(1);
+        let distance = || i32::try_from(n).map_err(|_| ParseError::new(line, column, "count too large"));
+        let (jump, times) = match cursor.bump() {
+            Some('h') => (Jump::Lateral(-distance()?), 1),
+            Some('l') => (Jump::Lateral(distance()?), 1),
+            Some('u') => (Jump::Up, n),
+            Some('d') => (Jump::Down, n),
+            Some('D') => (Jump::DownLast, n),
+            Some('H') => (Jump::FirstSibling, n),
+            Some('L') => (Jump::LastSibling, n),
+            Some('r') => (Jump::Root, n),
+            Some('n') => (Jump::Next, n),
+            Some('p') => (Jump::Prev, n),
+            Some('N') => (Jump::NextLeaf, n),
+            Some('P') => (Jump::PrevLeaf, n),
+            _ => return Err(ParseError::new(line, column, "expected a key or a path"))
+        };
+        jumps.extend(core::iter::repeat_n(jump, times));
+    }
+}
+
+/// Parses a step of a relative path: `..`, `.` or an index.
+fn step(cursor: &mut Cursor, jumps: &mut Vec<Jump>) -> Result<(), ParseError> {
+    if cursor.peek() == Some('.') {
+        cursor.bump();
+        if cursor.peek() == Some('.') {
+            cursor.bump();
+            jumps.push(Jump::Up);
+        }
+    } else {
+        jumps.push(Jump::Child(number(cursor)?));
+    }
+    Ok(())
+}
+
+/// Parses the steps of a relative path following its first one, each preceded by a `/`.
+/// A trailing `/` is allowed.
+fn steps(cursor: &mut Cursor, jumps: &mut Vec<Jump>) -> Result<(), ParseError> {
+    while cursor.peek() == Some('/') {
+        cursor.bump();
+        match cursor.peek() {
+            Some(c) if c == '.' || c.is_ascii_digit() => step(cursor, jumps)?,
+            _ => break
+        }
+    }
+    Ok(())
+}
+
+/// Parses an absolute path, from right before its leading `/`.
+fn absolute(cursor: &mut Cursor, jumps: &mut Vec<Jump>) -> Result<(), ParseError> {
+    let mut indices = Vec::new();
+    while cursor.peek() == Some('/') {
+        cursor.bump();
+        match cursor.peek() {
+            Some(c) if c.is_ascii_digit() => indices.push(number(cursor)?),
+            _ => break
+        }
+    }
+    jumps.push(Jump::ToPath(indices));
+    Ok(())
+}
+
+/// Parses a decimal number.
+fn number(cursor: &mut Cursor) -> Result<usize, ParseError> {
+    let mut n: usize = 0;
+    while let Some(d) = cursor.peek().and_then(|c| c.to_digit(10)) {
+        n = n.checked_mul(10).and_then(|n| n.checked_add(d as usize)).ok_or_else(|| cursor.error("number too large"))?;
+        cursor.bump();
+    }
+    Ok(n)
+}
+
+impl<T> Focus<T> {
+    /// Changes focus according to the movement command `s`.
+    /// Returns an error, leaving the focus unchanged, if the command isn't well-formed
+    /// or any of its jumps is impossible.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    /// use tt::focus::{JumpError, Focus};
+    /// use tt::command::CommandError;
+    ///
+    /// let mut tree = Tree::new(0);
+    /// tree.create_subtree(1);
+    /// tree.create_subtree(2);
+    /// tree.child_at_mut(1).unwrap().create_subtree(3);
+    ///
+    /// let mut focus = Focus::from(tree, None).unwrap();
+    /// focus.apply_str("d l d").unwrap();
+    /// assert_eq!(focus.focused().label(), &3);
+    /// focus.apply_str("../../0").unwrap();
+    /// assert_eq!(focus.focused().label(), &1);
+    ///
+    /// assert_eq!(focus.apply_str("uu"), Err(CommandError::Jump(JumpError::AtRoot)));
+    /// assert!(matches!(focus.apply_str("u?"), Err(CommandError::Parse(_))));
+    /// assert_eq!(focus.path(), &vec![0]);
+    /// ```
+    pub fn apply_str(&mut self, s: &str) -> Result<(), CommandError> {
+        self.jump_all(parse(s)?)?;
+        Ok(())
+    }
+}
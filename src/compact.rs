@@ -0,0 +1,103 @@
+//! A compact serde representation of trees, writing every tree as a
+//! `[label, [child, child, ...]]` pair instead of a struct with named fields.
+//!
+//! Either wrap a tree in `Compact`, or use this module with `#[serde(with = "tt::compact")]`.
+//! # Example
+//! ```
+//! use serde::{Serialize, Deserialize};
+//! use tt::tree::Tree;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Document {
+//!     #[serde(with = "tt::compact")]
+//!     outline: Tree<String>
+//! }
+//! ```
+
+use alloc::vec::Vec;
+use core::fmt;
+use core::marker::PhantomData;
+
+use serde::de::{self, Deserializer, Visitor, SeqAccess};
+use serde::ser::{Serializer, SerializeTuple};
+use serde::{Serialize, Deserialize};
+
+use super::tree::Tree;
+
+/// A tree (de)serializing in the compact representation.
+/// # Example
+/// ```
+/// use tt::tree::Tree;
+/// use tt::compact::Compact;
+///
+/// let mut tree = Tree::new(0);
+/// tree.create_subtree(1);
+///
+/// let compact = Compact::from(tree.clone());
+/// assert_eq!(Tree::from(compact), tree);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Compact<T>(pub Tree<T>);
+
+impl<T> From<Tree<T>> for Compact<T> {
+    fn from(tree: Tree<T>) -> Self { Self(tree) }
+}
+
+impl<T> From<Compact<T>> for Tree<T> {
+    fn from(compact: Compact<T>) -> Self { compact.0 }
+}
+
+impl<T: Serialize> Serialize for Compact<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize(&self.0, serializer)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Compact<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize(deserializer).map(Compact)
+    }
+}
+
+/// Serializes `tree` in the compact representation.
+pub fn serialize<T: Serialize, S: Serializer>(tree: &Tree<T>, serializer: S) -> Result<S::Ok, S::Error> {
+    CompactRef(tree).serialize(serializer)
+}
+
+/// Deserializes a tree from the compact representation.
+pub fn deserialize<'de, T: Deserialize<'de>, D: Deserializer<'de>>(deserializer: D) -> Result<Tree<T>, D::Error> {
+    deserializer.deserialize_tuple(2, TreeVisitor(PhantomData))
+}
+
+struct CompactRef<'a, T>(&'a Tree<T>);
+
+impl<'a, T: Serialize> Serialize for CompactRef<'a, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let children: Vec<CompactRef<T>> = self.0.children_iter().map(CompactRef).collect();
+        let mut tuple = serializer.serialize_tuple(2)?;
+        tuple.serialize_element(self.0.label())?;
+        tuple.serialize_element(&children)?;
+        tuple.end()
+    }
+}
+
+struct TreeVisitor<T>(PhantomData<T>);
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for TreeVisitor<T> {
+    type Value = Tree<T>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a [label, children] pair")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let label = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let children: Vec<Compact<T>> = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(1, &self))?;
+
+        let mut tree = Tree::new(label);
+        for child in children {
+            tree.attach_child(child.0);
+        }
+        Ok(tree)
+    }
+}
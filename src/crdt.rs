@@ -0,0 +1,362 @@
+//! A conflict-free replicated tree, after the move operation of Kleppmann et al.
+//!
+//! Every replica applies every operation, in any order and any amount of times, and replicas
+//! having applied the same operations hold the same tree. Operations are ordered by Lamport
+//! timestamps: one arriving late is applied by undoing the later ones, applying it and redoing
+//! them, and a move that would make a node its own ancestor is skipped.
+//!
+//! Creating, moving, relabelling and deleting are all moves: deleted nodes go below a hidden trash
+//! node. Siblings are ordered by dense position keys, so any of them can be inserted anywhere.
+//! The latest operation on a node wins as a whole, so relabelling a node concurrently with
+//! moving it may leave it where it was.
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::{vec, vec::Vec};
+
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
+use super::tree::{Path, Tree};
+
+/// A Lamport timestamp, unique to the operation and replica it comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Timestamp {
+    counter: u64,
+    replica: u64
+}
+
+impl Timestamp {
+    pub fn counter(self) -> u64 { self.counter }
+    pub fn replica(self) -> u64 { self.replica }
+}
+
+/// A node of a replicated tree, identified by the timestamp of the operation creating it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct NodeId(Timestamp);
+
+/// The root, which can't be moved.
+pub const ROOT: NodeId = NodeId(Timestamp { counter: 0, replica: 0 });
+/// The parent of deleted nodes, which never shows in the tree.
+const TRASH: NodeId = NodeId(Timestamp { counter: 0, replica: 1 });
+
+/// An operation moving `node` below `parent` with `label`, creating it if it doesn't exist yet.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Op<T> {
+    time: Timestamp,
+    node: NodeId,
+    parent: NodeId,
+    label: T,
+    position: Vec<u32>
+}
+
+impl<T> Op<T> {
+    pub fn time(&self) -> Timestamp { self.time }
+    pub fn node(&self) -> NodeId { self.node }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Node<T> {
+    parent: NodeId,
+    label: T,
+    position: Vec<u32>
+}
+
+/// An applied operation, along with the node it moved as it was before.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Entry<T> {
+    op: Op<T>,
+    old: Option<Node<T>>
+}
+
+/// A replica of a tree.
+/// Local edits apply right away and return the operation to send to the other replicas.
+/// # Example
+/// ```
+/// use tt::crdt::{Replica, ROOT};
+///
+/// let mut a = Replica::new(1, "root");
+/// let (x, op) = a.create(ROOT, 0, "x").unwrap();
+/// let mut ops = vec![op];
+/// let (y, op) = a.create(ROOT, 1, "y").unwrap();
+/// ops.push(op);
+///
+/// let mut b = Replica::new(2, "root");
+/// for op in &ops {
+///     b.apply(op.clone());
+/// }
+///
+/// // Concurrently moving each node below the other.
+/// let from_a = a.move_node(x, y, 0).unwrap();
+/// let from_b = b.move_node(y, x, 0).unwrap();
+/// a.apply(from_b);
+/// b.apply(from_a.clone());
+/// b.apply(from_a);
+///
+/// assert_eq!(a.to_tree(), b.to_tree());
+/// assert_eq!(a.to_tree().size(), 3);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Replica<T> {
+    id: u64,
+    clock: u64,
+    root: T,
+    nodes: BTreeMap<NodeId, Node<T>>,
+    /// The children of every parent, by position.
+    children: BTreeMap<NodeId, BTreeSet<(Vec<u32>, NodeId)>>,
+    log: Vec<Entry<T>>
+}
+
+impl<T: Clone> Replica<T> {
+    /// Constructs and returns a new `Replica` identified by `id`, holding only a root with `label`.
+    /// Replicas of a tree must have distinct identifiers and all start with the same root label.
+    pub fn new(id: u64, label: T) -> Self {
+        Self { id, clock: 0, root: label, nodes: BTreeMap::new(), children: BTreeMap::new(), log: Vec::new() }
+    }
+
+    pub fn id(&self) -> u64 { self.id }
+
+    /// Returns the label of `node`.
+    /// Returns none if it doesn't exist or is deleted.
+    pub fn label(&self, node: NodeId) -> Option<&T> {
+        match node {
+            ROOT => Some(&self.root),
+            node => self.contains(node).then(|| &self.nodes[&node].label)
+        }
+    }
+
+    /// Returns whether `node` exists and isn't deleted.
+    /// A node whose parent, or any of its ancestors, wasn't created yet doesn't exist until it is.
+    /// # Example
+    /// ```
+    /// use tt::crdt::{Replica, ROOT};
+    ///
+    /// let mut a = Replica::new(1, "root");
+    /// let (x, create_x) = a.create(ROOT, 0, "x").unwrap();
+    /// let (y, create_y) = a.create(x, 0, "y").unwrap();
+    ///
+    /// let mut b = Replica::new(2, "root");
+    /// b.apply(create_y);
+    /// assert!(!b.contains(y));
+    /// assert!(b.label(y).is_none());
+    /// assert!(b.path_of(y).is_none());
+    ///
+    /// b.apply(create_x);
+    /// assert_eq!(b.path_of(y), Some(vec![0, 0]));
+    /// assert_eq!(a.to_tree(), b.to_tree());
+    /// ```
+    pub fn contains(&self, mut node: NodeId) -> bool {
+        while node != ROOT {
+            match self.nodes.get(&node) {
+                Some(n) => node = n.parent,
+                None => return false
+            }
+        }
+        true
+    }
+
+    /// Returns the node at `path`.
+    /// Returns none if the path doesn't point to an existing point on the tree.
+    pub fn node_at(&self, path: &[usize]) -> Option<NodeId> {
+        path.iter().try_fold(ROOT, |node, &i| self.children(node).get(i).copied())
+    }
+
+    /// Returns the path of `node`.
+    /// Returns none if it doesn't exist or is deleted.
+    pub fn path_of(&self, mut node: NodeId) -> Option<Path> {
+        if !self.contains(node) {
+            return None;
+        }
+        let mut path = Vec::new();
+        while node != ROOT {
+            let Node { parent, position, .. } = &self.nodes[&node];
+            path.push(self.children[parent].range(..(position.clone(), node)).count());
+            node = *parent;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Creates a new child of `parent` at index `i`, clamped, returning it along with the operation.
+    /// Returns none if `parent` doesn't exist or is deleted.
+    pub fn create(&mut self, parent: NodeId, i: usize, label: T) -> Option<(NodeId, Op<T>)> {
+        let node = NodeId(Timestamp { counter: self.clock + 1, replica: self.id });
+        self.move_to(node, parent, i, label).map(|op| (node, op))
+    }
+
+    /// Moves `node` to be the child of `parent` at index `i`, clamped, returning the operation.
+    /// Returns none if either doesn't exist or is deleted, `node` is the root or `parent` lies within `node`.
+    pub fn move_node(&mut self, node: NodeId, parent: NodeId, i: usize) -> Option<Op<T>> {
+        if node == ROOT || !self.contains(node) || self.is_ancestor(node, parent) {
+            return None;
+        }
+        let label = self.nodes[&node].label.clone();
+        self.move_to(node, parent, i, label)
+    }
+
+    /// Replaces the label of `node`, returning the operation.
+    /// Returns none if it doesn't exist or is deleted, or is the root.
+    pub fn relabel(&mut self, node: NodeId, label: T) -> Option<Op<T>> {
+        if node == ROOT || !self.contains(node) {
+            return None;
+        }
+        let Node { parent, position, .. } = self.nodes[&node].clone();
+        let time = self.tick();
+        Some(self.local(Op { time, node, parent, label, position }))
+    }
+
+    /// Deletes `node` along with its descendants, returning the operation.
+    /// Returns none if it doesn't exist or is already deleted, or is the root.
+    pub fn delete(&mut self, node: NodeId) -> Option<Op<T>> {
+        if node == ROOT || !self.contains(node) {
+            return None;
+        }
+        let label = self.nodes[&node].label.clone();
+        let time = self.tick();
+        Some(self.local(Op { time, node, parent: TRASH, label, position: Vec::new() }))
+    }
+
+    /// Applies `op`, coming from any replica.
+    /// Applying an operation again has no effect.
+    pub fn apply(&mut self, op: Op<T>) {
+        self.clock = self.clock.max(op.time.counter);
+        let i = match self.log.binary_search_by_key(&op.time, |entry| entry.op.time) {
+            Ok(_) => return,
+            Err(i) => i
+        };
+
+        let later: Vec<Entry<T>> = self.log.drain(i..).collect();
+        for entry in later.iter().rev() {
+            self.set(entry.op.node, entry.old.clone());
+        }
+        self.perform(op);
+        for entry in later {
+            self.perform(entry.op);
+        }
+    }
+
+    /// Returns the tree as it currently is.
+    pub fn to_tree(&self) -> Tree<T> {
+        let mut tree = Tree::new(self.root.clone());
+        let mut stack = vec![(ROOT, Vec::new())];
+        while let Some((node, path)) = stack.pop() {
+            for (i, child) in self.children(node).into_iter().enumerate() {
+                let mut path: Path = path.clone();
+                tree.get_mut(&path).unwrap().create_subtree(self.nodes[&child].label.clone());
+                path.push(i);
+                stack.push((child, path));
+            }
+        }
+        tree
+    }
+
+    /// Returns the children of `node`, in order.
+    fn children(&self, node: NodeId) -> Vec<NodeId> {
+        self.children.get(&node).map_or(Vec::new(), |children| children.iter().map(|(_, id)| *id).collect())
+    }
+
+    /// Replaces the node `id`, removing it if `node` is none, and keeps the children index up to date.
+    fn set(&mut self, id: NodeId, node: Option<Node<T>>) {
+        if let Some(old) = self.nodes.remove(&id) {
+            let siblings = self.children.get_mut(&old.parent).unwrap();
+            siblings.remove(&(old.position, id));
+            if siblings.is_empty() {
+                self.children.remove(&old.parent);
+            }
+        }
+        if let Some(node) = node {
+            self.children.entry(node.parent).or_default().insert((node.position.clone(), id));
+            self.nodes.insert(id, node);
+        }
+    }
+
+    /// Returns whether `ancestor` lies on the way from `node` up to the root, `node` included.
+    fn is_ancestor(&self, ancestor: NodeId, mut node: NodeId) -> bool {
+        loop {
+            if node == ancestor {
+                return true;
+            }
+            match self.nodes.get(&node) {
+                Some(n) => node = n.parent,
+                None => return false
+            }
+        }
+    }
+
+    fn tick(&mut self) -> Timestamp {
+        self.clock += 1;
+        Timestamp { counter: self.clock, replica: self.id }
+    }
+
+    fn move_to(&mut self, node: NodeId, parent: NodeId, i: usize, label: T) -> Option<Op<T>> {
+        if !self.contains(parent) {
+            return None;
+        }
+        let moving = self.nodes.get(&node).is_some_and(|n| n.parent == parent);
+        let len = self.children.get(&parent).map_or(0, BTreeSet::len) - moving as usize;
+        let mut siblings = self.children.get(&parent).into_iter().flatten()
+            .filter(|(_, n)| *n != node)
+            .map(|(position, _)| &position[..]);
+        let (lo, hi) = match i.min(len) {
+            0 => (None, siblings.next()),
+            i if i == len => (siblings.next_back(), None),
+            i => (siblings.nth(i - 1), siblings.next())
+        };
+        let position = between(lo.unwrap_or(&[]), hi);
+        let time = self.tick();
+        Some(self.local(Op { time, node, parent, label, position }))
+    }
+
+    fn local(&mut self, op: Op<T>) -> Op<T> {
+        self.apply(op.clone());
+        op
+    }
+
+    /// Performs `op` on top of the log, skipping it if it would make a node its own ancestor.
+    fn perform(&mut self, op: Op<T>) {
+        let old = self.nodes.get(&op.node).cloned();
+        if op.node != ROOT && op.node != TRASH && !self.is_ancestor(op.node, op.parent) {
+            self.set(op.node, Some(Node { parent: op.parent, label: op.label.clone(), position: op.position.clone() }));
+        }
+        self.log.push(Entry { op, old });
+    }
+}
+
+/// Returns a position key ordered strictly between `lo` and `hi`, none standing for no bound.
+/// Siblings created concurrently may share a key: between those, or when no key lies between
+/// `lo` and `hi` such as `[5]` and `[5, 0]`, returns a key right after `lo`.
+/// Without an upper bound, keys step by a fixed amount rather than halving the gap,
+/// so that appending siblings keeps their keys short.
+fn between(lo: &[u32], hi: Option<&[u32]>) -> Vec<u32> {
+    let after = || {
+        let mut key = lo.to_vec();
+        key.push(1 << 31);
+        key
+    };
+    if hi.is_some_and(|hi| hi <= lo) {
+        return after();
+    }
+    let mut key = Vec::new();
+    let (mut lo_tight, mut hi_tight) = (true, hi.is_some());
+    for i in 0.. {
+        if hi_tight && hi.is_some_and(|hi| i >= hi.len()) {
+            return after();
+        }
+        let l = if lo_tight { lo.get(i).map_or(0, |&l| l as u64) } else { 0 };
+        let h = match hi {
+            Some(hi) if hi_tight => hi[i] as u64,
+            _ => 1 << 32
+        };
+        if h > l + 1 {
+            let step = (h - l) / 2;
+            key.push((l + if h == 1 << 32 { step.min(1 << 16) } else { step }) as u32);
+            break;
+        }
+        key.push(l as u32);
+        lo_tight &= i < lo.len();
+        hi_tight &= l == h;
+    }
+    key
+}
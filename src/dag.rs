@@ -0,0 +1,152 @@
+//! Hash-consed trees, storing each distinct subtree once however often it appears.
+//!
+//! Nodes live in one table, children before their parents, and refer to their children by index,
+//! so identical subtrees become the same entry and the tree a directed acyclic graph.
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::hash::Hash;
+
+use super::hash::combine;
+use super::persistent::PersistentTree;
+use super::tree::Tree;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct DagNode<T> {
+    label: T,
+    children: Vec<usize>
+}
+
+/// A tree of which every distinct subtree is stored only once.
+/// # Example
+/// ```
+/// use tt::tree::Tree;
+/// use tt::dag::DagTree;
+///
+/// let tree = Tree::<String>::from_sexpr("(list (item (a) (b)) (item (a) (b)) (item (a)))").unwrap();
+/// let dag = DagTree::from(tree.clone());
+///
+/// let stats = dag.stats();
+/// assert_eq!((stats.nodes, stats.unique, stats.saved()), (9, 5, 4));
+///
+/// let items = dag.children(dag.root());
+/// assert_eq!(items[0], items[1]);
+/// assert_ne!(items[1], items[2]);
+/// assert_eq!(dag.children(items[2])[0], dag.children(items[0])[0]);
+///
+/// assert_eq!(dag.to_tree(), tree);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DagTree<T> {
+    nodes: Vec<DagNode<T>>,
+    size: usize
+}
+
+/// How much a `DagTree` saves over the tree it was built from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct DagStats {
+    /// The number of nodes of the tree.
+    pub nodes: usize,
+    /// The number of distinct subtrees, each stored once.
+    pub unique: usize
+}
+
+impl DagStats {
+    /// Returns how many nodes were left out by sharing.
+    pub fn saved(&self) -> usize { self.nodes - self.unique }
+}
+
+impl<T> DagTree<T> {
+    /// Returns the index of the root, the last node of the table.
+    pub fn root(&self) -> usize { self.nodes.len() - 1 }
+
+    /// Returns the number of distinct subtrees, each stored once.
+    pub fn len(&self) -> usize { self.nodes.len() }
+    /// Returns whether the table is empty, which it never is since it always holds a root.
+    pub fn is_empty(&self) -> bool { self.nodes.is_empty() }
+    /// Returns the total number of nodes of the tree, counting shared subtrees every time they appear.
+    pub fn size(&self) -> usize { self.size }
+
+    pub fn label(&self, i: usize) -> Option<&T> { self.nodes.get(i).map(|n| &n.label) }
+
+    /// Returns the indices of the children of node `i`, in order.
+    /// Returns an empty slice if there is no such node.
+    pub fn children(&self, i: usize) -> &[usize] {
+        self.nodes.get(i).map_or(&[], |n| &n.children)
+    }
+
+    /// Returns how many nodes are stored against how many the tree holds.
+    pub fn stats(&self) -> DagStats {
+        DagStats { nodes: self.size, unique: self.nodes.len() }
+    }
+}
+
+impl<T: Clone> DagTree<T> {
+    /// Returns a `Tree` holding copies of every label, expanding shared subtrees again.
+    pub fn to_tree(&self) -> Tree<T> {
+        self.expand(self.root())
+    }
+
+    /// Returns a `PersistentTree` in which every occurrence of a shared subtree is the very same node.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    /// use tt::dag::DagTree;
+    ///
+    /// let tree = Tree::<String>::from_sexpr("(list (item (a)) (item (a)))").unwrap();
+    /// let shared = DagTree::from(tree.clone()).to_persistent();
+    ///
+    /// assert!(shared.child_at(0).unwrap().ptr_eq(shared.child_at(1).unwrap()));
+    /// assert_eq!(shared.to_tree(), tree);
+    /// ```
+    pub fn to_persistent(&self) -> PersistentTree<T> {
+        let mut built: Vec<PersistentTree<T>> = Vec::with_capacity(self.nodes.len());
+        for node in &self.nodes {
+            let children = node.children.iter().map(|&c| built[c].clone()).collect();
+            built.push(PersistentTree::from_parts(node.label.clone(), children));
+        }
+        built.pop().expect("a dag tree always holds a root")
+    }
+
+    fn expand(&self, i: usize) -> Tree<T> {
+        let node = &self.nodes[i];
+        let mut tree = Tree::new(node.label.clone());
+        for &child in &node.children {
+            tree.attach_child(self.expand(child));
+        }
+        tree
+    }
+}
+
+impl<T: Hash + Eq> From<Tree<T>> for DagTree<T> {
+    fn from(tree: Tree<T>) -> Self {
+        let mut dag = DagTree { nodes: Vec::new(), size: 0 };
+        let mut table = BTreeMap::new();
+        dag.intern(tree, &mut table);
+        dag
+    }
+}
+
+impl<T: Hash + Eq> DagTree<T> {
+    /// Adds `tree` to the table unless it is there already, returning its index.
+    /// `table` lists the nodes by the hash of their label and children.
+    fn intern(&mut self, tree: Tree<T>, table: &mut BTreeMap<u64, Vec<usize>>) -> usize {
+        let (label, children) = tree.into_parts();
+        let children: Vec<usize> = children.into_iter().map(|child| self.intern(child, table)).collect();
+        self.size += 1;
+
+        let hash = combine(&label, children.iter().map(|&c| c as u64));
+        let bucket = table.entry(hash).or_default();
+        let nodes = &self.nodes;
+        if let Some(&i) = bucket.iter().find(|&&i| nodes[i].label == label && nodes[i].children == children) {
+            return i;
+        }
+        bucket.push(self.nodes.len());
+        self.nodes.push(DagNode { label, children });
+        self.nodes.len() - 1
+    }
+}
+
+impl<T: Clone> From<DagTree<T>> for Tree<T> {
+    fn from(dag: DagTree<T>) -> Self { dag.to_tree() }
+}
@@ -0,0 +1,294 @@
+//! Differences between trees, as patches of edits turning one tree into another.
+//!
+//! Siblings are matched by label, in order where possible: unmatched old ones are deleted,
+//! unmatched new ones inserted, siblings found out of order are moved and unmatched siblings
+//! left between the same matched ones are relabelled in place. Moves are only detected among
+//! siblings, a subtree moving to another parent being deleted and inserted again.
+
+use alloc::{vec, vec::Vec};
+use core::error::Error;
+use core::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
+use super::tree::{Path, Tree};
+
+/// An edit of a tree. Paths point into the tree as left by the edits before.
+/// * `Op::Insert(at, tree)` - Inserts `tree` at `at`, shifting later siblings along.
+/// * `Op::Delete(at)` - Removes the subtree at `at`.
+/// * `Op::Relabel(at, label)` - Replaces the label of the subtree at `at`.
+/// * `Op::Move(from, to)` - Removes the subtree at `from` and inserts it back at `to`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Op<T> {
+    Insert(Path, Tree<T>),
+    Delete(Path),
+    Relabel(Path, T),
+    Move(Path, Path)
+}
+
+/// A list of edits, applied in order by `Tree::apply_patch`.
+/// (De)serializes as a plain sequence of edits.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(transparent))]
+pub struct Patch<T> {
+    ops: Vec<Op<T>>
+}
+
+impl<T> Patch<T> {
+    /// Constructs and returns a new, empty `Patch`.
+    pub fn new() -> Self {
+        Self { ops: Vec::new() }
+    }
+
+    pub fn ops(&self) -> &[Op<T>] { &self.ops }
+    /// Returns how many edits this patch contains.
+    pub fn len(&self) -> usize { self.ops.len() }
+    /// Returns whether this patch contains no edits, leaving trees unchanged.
+    pub fn is_empty(&self) -> bool { self.ops.is_empty() }
+
+    /// Appends `op` as the last edit.
+    pub fn push(&mut self, op: Op<T>) {
+        self.ops.push(op);
+    }
+}
+
+impl<T> From<Vec<Op<T>>> for Patch<T> {
+    fn from(ops: Vec<Op<T>>) -> Self { Self { ops } }
+}
+
+impl<T> From<Patch<T>> for Vec<Op<T>> {
+    fn from(patch: Patch<T>) -> Self { patch.ops }
+}
+
+impl<T> IntoIterator for Patch<T> {
+    type Item = Op<T>;
+    type IntoIter = alloc::vec::IntoIter<Op<T>>;
+
+    fn into_iter(self) -> Self::IntoIter { self.ops.into_iter() }
+}
+
+/// The reason a patch couldn't be applied: one of its edits points to no point on the tree
+/// as left by the edits before it, or removes or inserts the root.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatchError {
+    index: usize
+}
+
+impl PatchError {
+    /// Returns the index of the edit that couldn't be applied.
+    pub fn index(&self) -> usize { self.index }
+}
+
+impl fmt::Display for PatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "edit {} of the patch doesn't fit the tree", self.index)
+    }
+}
+
+impl Error for PatchError {}
+
+/// Returns the patch turning `old` into `new`.
+/// # Example
+/// ```
+/// use tt::tree::Tree;
+/// use tt::diff::{self, Op};
+///
+/// let old = Tree::<String>::from_sexpr("(todo (a) (b) (c (d)) (g))").unwrap();
+/// let new = Tree::<String>::from_sexpr("(todo (c (e)) (a) (x) (g) (f))").unwrap();
+///
+/// let patch = diff::diff(&old, &new);
+/// assert_eq!(patch.ops(), [
+///     Op::Move(vec![2], vec![0]),
+///     Op::Insert(vec![4], Tree::new(String::from("f"))),
+///     Op::Relabel(vec![0, 0], String::from("e")),
+///     Op::Relabel(vec![2], String::from("x"))
+/// ]);
+/// assert!(diff::diff(&old, &old).is_empty());
+///
+/// let mut tree = old.clone();
+/// tree.apply_patch(&patch).unwrap();
+/// assert_eq!(tree, new);
+/// ```
+pub fn diff<T: Clone + Eq>(old: &Tree<T>, new: &Tree<T>) -> Patch<T> {
+    let mut ops = Vec::new();
+    diff_at(&mut Path::new(), old, new, &mut ops);
+    Patch { ops }
+}
+
+impl<T: Clone> Tree<T> {
+    /// Applies the edits of `patch` in order.
+    /// Returns an error, leaving the tree unchanged, if any of them doesn't fit the tree.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    /// use tt::diff::{Op, Patch};
+    ///
+    /// let mut tree = Tree::new(0);
+    /// tree.create_subtree(1);
+    ///
+    /// let patch = Patch::from(vec![Op::Insert(vec![0], Tree::new(2)), Op::Delete(vec![1, 0])]);
+    /// assert_eq!(tree.apply_patch(&patch).unwrap_err().index(), 1);
+    /// assert_eq!(tree.children(), 1);
+    ///
+    /// let patch = Patch::from(vec![Op::Insert(vec![0], Tree::new(2)), Op::Move(vec![1], vec![0, 0])]);
+    /// tree.apply_patch(&patch).unwrap();
+    /// assert_eq!(tree.iter().collect::<Vec<_>>(), vec![&0, &2, &1]);
+    /// ```
+    pub fn apply_patch(&mut self, patch: &Patch<T>) -> Result<(), PatchError> {
+        let mut inverses = Vec::with_capacity(patch.len());
+        for (index, op) in patch.ops.iter().enumerate() {
+            match self.apply_op(op.clone()) {
+                Some(inverse) => inverses.push(inverse),
+                None => {
+                    for inverse in inverses.into_iter().rev() {
+                        self.apply_op(inverse);
+                    }
+                    return Err(PatchError { index });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T> Tree<T> {
+    /// Applies `op`, returning the edit undoing it.
+    /// Returns none, leaving the tree unchanged, if it doesn't fit the tree.
+    fn apply_op(&mut self, op: Op<T>) -> Option<Op<T>> {
+        match op {
+            Op::Insert(at, tree) => {
+                let (&i, parent) = at.split_last()?;
+                let parent = self.get_mut(parent).filter(|p| i <= p.children())?;
+                parent.insert_tree_at(i, tree);
+                Some(Op::Delete(at))
+            }
+            Op::Delete(at) => {
+                let (&i, parent) = at.split_last()?;
+                let removed = self.get_mut(parent)?.remove_child(i)?;
+                Some(Op::Insert(at, removed))
+            }
+            Op::Relabel(at, label) => {
+                let old = self.get_mut(&at)?.set_label(label);
+                Some(Op::Relabel(at, old))
+            }
+            Op::Move(from, to) => {
+                let ((&i, parent), (&j, dest)) = (from.split_last()?, to.split_last()?);
+                let subtree = self.get_mut(parent)?.remove_child(i)?;
+                match self.get_mut(dest).filter(|d| j <= d.children()) {
+                    Some(dest) => dest.insert_tree_at(j, subtree),
+                    None => {
+                        self.get_mut(parent)?.insert_tree_at(i, subtree);
+                        return None;
+                    }
+                }
+                Some(Op::Move(to, from))
+            }
+        }
+    }
+}
+
+/// Pushes the edits turning `old`, lying at `path`, into `new`.
+fn diff_at<T: Clone + Eq>(path: &mut Path, old: &Tree<T>, new: &Tree<T>, ops: &mut Vec<Op<T>>) {
+    if old.label() != new.label() {
+        ops.push(Op::Relabel(path.clone(), new.label().clone()));
+    }
+
+    let (old, new) = (old.children_iter().as_slice(), new.children_iter().as_slice());
+    let pairs = matching(old, new);
+    let mut kept = vec![false; old.len()];
+    for &i in pairs.iter().flatten() {
+        kept[i] = true;
+    }
+
+    for i in (0..old.len()).rev().filter(|&i| !kept[i]) {
+        ops.push(Op::Delete(child(path, i)));
+    }
+
+    let mut current: Vec<Option<usize>> = (0..old.len()).filter(|&i| kept[i]).map(Some).collect();
+    for (j, &pair) in pairs.iter().enumerate() {
+        match pair {
+            Some(i) => {
+                let k = j + current[j..].iter().position(|&c| c == Some(i)).unwrap();
+                if k != j {
+                    ops.push(Op::Move(child(path, k), child(path, j)));
+                    let moved = current.remove(k);
+                    current.insert(j, moved);
+                }
+            }
+            None => {
+                ops.push(Op::Insert(child(path, j), new[j].clone()));
+                current.insert(j, None);
+            }
+        }
+    }
+
+    for (j, &pair) in pairs.iter().enumerate() {
+        if let Some(i) = pair {
+            path.push(j);
+            diff_at(path, &old[i], &new[j], ops);
+            path.pop();
+        }
+    }
+}
+
+/// Returns, for each of `new`, the index of the tree of `old` it is matched with.
+pub(crate) fn matching<T: Eq>(old: &[Tree<T>], new: &[Tree<T>]) -> Vec<Option<usize>> {
+    let (n, m) = (old.len(), new.len());
+    let same = |i: usize, j: usize| old[i].label() == new[j].label();
+
+    // The longest common subsequence of labels, kept in order.
+    let mut lengths = vec![vec![0; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lengths[i][j] = if same(i, j) { lengths[i + 1][j + 1] + 1 } else { lengths[i + 1][j].max(lengths[i][j + 1]) };
+        }
+    }
+
+    let mut pairs = vec![None; m];
+    let mut used = vec![false; n];
+    let mut anchors = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if same(i, j) {
+            pairs[j] = Some(i);
+            used[i] = true;
+            anchors.push((i, j));
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] > lengths[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+
+    // Other trees with the same label, moved.
+    for (j, pair) in pairs.iter_mut().enumerate().filter(|(_, pair)| pair.is_none()) {
+        if let Some(i) = (0..n).find(|&i| !used[i] && same(i, j)) {
+            *pair = Some(i);
+            used[i] = true;
+        }
+    }
+
+    // Other trees between the same anchors, relabelled.
+    anchors.push((n, m));
+    let (mut lo, mut lo_j) = (0, 0);
+    for (hi, hi_j) in anchors {
+        let mut free = (lo..hi).filter(|&i| !used[i]);
+        for pair in pairs[lo_j..hi_j].iter_mut().filter(|pair| pair.is_none()) {
+            *pair = free.next();
+        }
+        lo = hi + 1;
+        lo_j = hi_j + 1;
+    }
+    pairs
+}
+
+/// Returns the path of the `i`th child of the subtree at `path`.
+fn child(path: &[usize], i: usize) -> Path {
+    let mut child = path.to_vec();
+    child.push(i);
+    child
+}
@@ -0,0 +1,383 @@
+use alloc::{boxed::Box, string::{String, ToString}, vec::Vec, format};
+use core::fmt::Display;
+
+use super::layout::Point;
+use super::tree::Tree;
+
+/// A function producing the identifier of the node at a path.
+type IdFormatter = Box<dyn Fn(&[usize]) -> String>;
+
+/// Options for rendering trees in the Graphviz DOT language.
+/// # Example
+/// ```
+/// use tt::tree::Tree;
+/// use tt::export::DotOptions;
+///
+/// let mut tree = Tree::new("root");
+/// tree.create_subtree("say \"hi\"");
+///
+/// assert_eq!(tree.to_dot(), "digraph {
+///     n [label=\"root\"];
+///     n0 [label=\"say \\\"hi\\\"\"];
+///     n -> n0;
+/// }
+/// ");
+///
+/// let options = DotOptions::new()
+///     .shape("box")
+///     .id(|path| format!("node{}", path.len()));
+/// assert_eq!(tree.to_dot_with(&options), "digraph {
+///     node [shape=box];
+///     node0 [label=\"root\"];
+///     node1 [label=\"say \\\"hi\\\"\"];
+///     node0 -> node1;
+/// }
+/// ");
+/// ```
+pub struct DotOptions<T> {
+    shape: Option<String>,
+    id: IdFormatter,
+    label: Box<dyn Fn(&T) -> String>
+}
+
+impl<T: Display> DotOptions<T> {
+    /// Constructs and returns new `DotOptions` formatting labels with `Display`.
+    /// By default nodes have the default Graphviz shape and are identified by their path.
+    pub fn new() -> Self {
+        Self::with_label(|label: &T| label.to_string())
+    }
+}
+
+impl<T: Display> Default for DotOptions<T> {
+    fn default() -> Self { Self::new() }
+}
+
+impl<T> DotOptions<T> {
+    /// Constructs and returns new `DotOptions` formatting labels with `label`.
+    pub fn with_label<F: Fn(&T) -> String + 'static>(label: F) -> Self {
+        Self {
+            shape: None,
+            id: Box::new(default_id),
+            label: Box::new(label)
+        }
+    }
+
+    /// Sets the shape of all nodes, e.g. `"box"` or `"ellipse"`.
+    pub fn shape(mut self, shape: &str) -> Self {
+        self.shape = Some(shape.to_string());
+        self
+    }
+
+    /// Sets the function producing the identifier of the node at a path.
+    /// Identifiers must be unique and valid DOT identifiers.
+    pub fn id<F: Fn(&[usize]) -> String + 'static>(mut self, id: F) -> Self {
+        self.id = Box::new(id);
+        self
+    }
+
+    /// Sets the function turning labels into text.
+    pub fn label<F: Fn(&T) -> String + 'static>(mut self, label: F) -> Self {
+        self.label = Box::new(label);
+        self
+    }
+}
+
+/// Options for rendering trees as Mermaid flowcharts.
+/// # Example
+/// ```
+/// use tt::tree::Tree;
+/// use tt::export::MermaidOptions;
+///
+/// let mut tree = Tree::new("root");
+/// tree.create_subtree("issue #1 \"urgent\"");
+///
+/// assert_eq!(tree.to_mermaid(), "graph TD
+///     n[\"root\"]
+///     n0[\"issue #35;1 #quot;urgent#quot;\"]
+///     n --> n0
+/// ");
+///
+/// let options = MermaidOptions::new().direction("LR").label(|label: &&str| label.len().to_string());
+/// assert_eq!(tree.to_mermaid_with(&options), "graph LR
+///     n[\"4\"]
+///     n0[\"17\"]
+///     n --> n0
+/// ");
+/// ```
+pub struct MermaidOptions<T> {
+    direction: String,
+    id: IdFormatter,
+    label: Box<dyn Fn(&T) -> String>
+}
+
+impl<T: Display> MermaidOptions<T> {
+    /// Constructs and returns new `MermaidOptions` formatting labels with `Display`.
+    /// By default the flowchart is drawn top-down and nodes are identified by their path.
+    pub fn new() -> Self {
+        Self::with_label(|label: &T| label.to_string())
+    }
+}
+
+impl<T: Display> Default for MermaidOptions<T> {
+    fn default() -> Self { Self::new() }
+}
+
+impl<T> MermaidOptions<T> {
+    /// Constructs and returns new `MermaidOptions` formatting labels with `label`.
+    pub fn with_label<F: Fn(&T) -> String + 'static>(label: F) -> Self {
+        Self {
+            direction: String::from("TD"),
+            id: Box::new(default_id),
+            label: Box::new(label)
+        }
+    }
+
+    /// Sets the direction of the flowchart, e.g. `"TD"` or `"LR"`.
+    pub fn direction(mut self, direction: &str) -> Self {
+        self.direction = direction.to_string();
+        self
+    }
+
+    /// Sets the function producing the identifier of the node at a path.
+    /// Identifiers must be unique and valid Mermaid identifiers.
+    pub fn id<F: Fn(&[usize]) -> String + 'static>(mut self, id: F) -> Self {
+        self.id = Box::new(id);
+        self
+    }
+
+    /// Sets the function turning labels into text.
+    pub fn label<F: Fn(&T) -> String + 'static>(mut self, label: F) -> Self {
+        self.label = Box::new(label);
+        self
+    }
+}
+
+/// Options for rendering trees as SVG diagrams, with nodes drawn as circles labelled
+/// underneath and joined to their parents by lines.
+/// # Example
+/// ```
+/// use tt::tree::Tree;
+/// use tt::export::SvgOptions;
+///
+/// let mut tree = Tree::new("a & b");
+/// tree.create_subtree("c");
+/// tree.create_subtree("d");
+///
+/// let svg = tree.to_svg();
+/// assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"160\" height=\"120\""));
+/// assert!(svg.contains("<line x1=\"80\" y1=\"30\" x2=\"40\" y2=\"90\"/>"));
+/// assert!(svg.contains(">a &amp; b</text>"));
+///
+/// let options = SvgOptions::new().spacing(100.0).level_height(40.0);
+/// assert!(tree.to_svg_with(&options).contains("width=\"200\" height=\"100\""));
+/// ```
+pub struct SvgOptions<T> {
+    spacing: f64,
+    level_height: f64,
+    radius: f64,
+    font_size: f64,
+    label: Box<dyn Fn(&T) -> String>
+}
+
+impl<T: Display> SvgOptions<T> {
+    /// Constructs and returns new `SvgOptions` formatting labels with `Display`.
+    /// By default nodes are 80 apart horizontally and 60 apart vertically, with a radius
+    /// of 6 and labels of size 12.
+    pub fn new() -> Self {
+        Self::with_label(|label: &T| label.to_string())
+    }
+}
+
+impl<T: Display> Default for SvgOptions<T> {
+    fn default() -> Self { Self::new() }
+}
+
+impl<T> SvgOptions<T> {
+    /// Constructs and returns new `SvgOptions` formatting labels with `label`.
+    pub fn with_label<F: Fn(&T) -> String + 'static>(label: F) -> Self {
+        Self {
+            spacing: 80.0,
+            level_height: 60.0,
+            radius: 6.0,
+            font_size: 12.0,
+            label: Box::new(label)
+        }
+    }
+
+    /// Sets the horizontal distance between neighbouring leaves.
+    pub fn spacing(mut self, spacing: f64) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// Sets the vertical distance between levels.
+    pub fn level_height(mut self, level_height: f64) -> Self {
+        self.level_height = level_height;
+        self
+    }
+
+    /// Sets the radius of the circles drawn for nodes.
+    pub fn radius(mut self, radius: f64) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Sets the font size of labels.
+    pub fn font_size(mut self, font_size: f64) -> Self {
+        self.font_size = font_size;
+        self
+    }
+
+    /// Sets the function turning labels into text.
+    pub fn label<F: Fn(&T) -> String + 'static>(mut self, label: F) -> Self {
+        self.label = Box::new(label);
+        self
+    }
+}
+
+/// Returns the identifier `n` followed by the path indices joined with `_`.
+fn default_id(path: &[usize]) -> String {
+    let indices: Vec<String> = path.iter().map(|i| i.to_string()).collect();
+    format!("n{}", indices.join("_"))
+}
+
+/// Escapes every character of `label` for which `escape` returns a replacement.
+fn escape<F: Fn(char) -> Option<&'static str>>(label: &str, escape: F) -> String {
+    let mut escaped = String::with_capacity(label.len());
+    for c in label.chars() {
+        match escape(c) {
+            Some(replacement) => escaped.push_str(replacement),
+            None              => escaped.push(c)
+        }
+    }
+    escaped
+}
+
+/// Escapes a label for use within a double-quoted DOT string.
+fn escape_dot(label: &str) -> String {
+    escape(label, |c| match c {
+        '"'  => Some("\\\""),
+        '\\' => Some("\\\\"),
+        '\n' => Some("\\n"),
+        _    => None
+    })
+}
+
+/// Escapes a label for use within SVG text.
+fn escape_xml(label: &str) -> String {
+    escape(label, |c| match c {
+        '&' => Some("&amp;"),
+        '<' => Some("&lt;"),
+        '>' => Some("&gt;"),
+        '"' => Some("&quot;"),
+        _   => None
+    })
+}
+
+/// Escapes a label for use within a double-quoted Mermaid node text.
+fn escape_mermaid(label: &str) -> String {
+    escape(label, |c| match c {
+        '"'  => Some("#quot;"),
+        '#'  => Some("#35;"),
+        '\n' => Some("<br>"),
+        _    => None
+    })
+}
+
+impl<T: Display> Tree<T> {
+    /// Renders the tree as a Graphviz DOT digraph with default `DotOptions`.
+    pub fn to_dot(&self) -> String {
+        self.to_dot_with(&DotOptions::new())
+    }
+
+    /// Renders the tree as a Mermaid flowchart with default `MermaidOptions`.
+    pub fn to_mermaid(&self) -> String {
+        self.to_mermaid_with(&MermaidOptions::new())
+    }
+
+    /// Renders the tree as an SVG diagram with default `SvgOptions`.
+    pub fn to_svg(&self) -> String {
+        self.to_svg_with(&SvgOptions::new())
+    }
+}
+
+impl<T> Tree<T> {
+    /// Renders the tree as a Graphviz DOT digraph according to `options`.
+    pub fn to_dot_with(&self, options: &DotOptions<T>) -> String {
+        let mut dot = String::from("digraph {\n");
+        if let Some(shape) = &options.shape {
+            dot.push_str(&format!("    node [shape={}];\n", shape));
+        }
+
+        let mut edges = String::new();
+        for (path, label) in self.iter_paths() {
+            let id = (options.id)(&path);
+            dot.push_str(&format!("    {} [label=\"{}\"];\n", id, escape_dot(&(options.label)(label))));
+            if let Some((_, parent)) = path.split_last() {
+                edges.push_str(&format!("    {} -> {};\n", (options.id)(parent), id));
+            }
+        }
+
+        dot.push_str(&edges);
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Renders the tree as a Mermaid flowchart according to `options`.
+    pub fn to_mermaid_with(&self, options: &MermaidOptions<T>) -> String {
+        let mut mermaid = format!("graph {}\n", options.direction);
+        let mut edges = String::new();
+        for (path, label) in self.iter_paths() {
+            let id = (options.id)(&path);
+            mermaid.push_str(&format!("    {}[\"{}\"]\n", id, escape_mermaid(&(options.label)(label))));
+            if let Some((_, parent)) = path.split_last() {
+                edges.push_str(&format!("    {} --> {}\n", (options.id)(parent), id));
+            }
+        }
+
+        mermaid.push_str(&edges);
+        mermaid
+    }
+
+    /// Renders the tree as an SVG diagram according to `options`, laid out by `Tree::layout`.
+    pub fn to_svg_with(&self, options: &SvgOptions<T>) -> String {
+        let layout = self.layout();
+        let (margin_x, margin_y) = (options.spacing / 2.0, options.radius + 2.0 * options.font_size);
+        let point = |p: &Point| (margin_x + p.x * options.spacing, margin_y + p.y * options.level_height);
+
+        let right = layout.iter().map(|(_, p)| p.x).fold(0.0, f64::max);
+        let width = (right + 1.0) * options.spacing;
+        let height = 2.0 * margin_y + self.height() as f64 * options.level_height;
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">\n",
+            width, height, width, height
+        );
+
+        let mut edges = String::new();
+        let mut nodes = String::new();
+        let mut labels = String::new();
+        let mut ancestors = Vec::new();
+        for (path, (label, p)) in layout.iter_paths() {
+            let (cx, cy) = point(p);
+            ancestors.truncate(path.len());
+            if let Some(&(px, py)) = ancestors.last() {
+                edges.push_str(&format!("    <line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\"/>\n", px, py, cx, cy));
+            }
+            ancestors.push((cx, cy));
+            nodes.push_str(&format!("    <circle cx=\"{}\" cy=\"{}\" r=\"{}\"/>\n", cx, cy, options.radius));
+            labels.push_str(&format!(
+                "    <text x=\"{}\" y=\"{}\">{}</text>\n",
+                cx, cy + options.radius + options.font_size, escape_xml(&(options.label)(label))
+            ));
+        }
+
+        svg.push_str(&format!("  <g stroke=\"black\">\n{}  </g>\n", edges));
+        svg.push_str(&format!("  <g fill=\"white\" stroke=\"black\">\n{}  </g>\n", nodes));
+        svg.push_str(&format!(
+            "  <g text-anchor=\"middle\" font-family=\"sans-serif\" font-size=\"{}\">\n{}  </g>\n",
+            options.font_size, labels
+        ));
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
@@ -0,0 +1,139 @@
+//! A flat tree, storing every node in one contiguous `Vec` in depth-first pre-order
+//! and linking them by first-child and next-sibling indices.
+//! Traversals walk memory front to back, suiting large, read-heavy trees.
+
+use alloc::{vec, vec::Vec};
+use core::slice;
+
+use super::tree::Tree;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct FlatNode<T> {
+    label: T,
+    first_child: Option<usize>,
+    next_sibling: Option<usize>
+}
+
+/// A tree stored as one contiguous vector of nodes, the root at index `0`.
+/// Nodes are laid out in depth-first pre-order, so the first child of
+/// a node immediately follows it.
+/// # Example
+/// ```
+/// use tt::tree::Tree;
+/// use tt::flat::FlatTree;
+///
+/// let mut tree = Tree::new("root");
+/// tree.create_subtree("a");
+/// tree.child_at_mut(0).unwrap().create_subtree("b");
+/// tree.create_subtree("c");
+///
+/// let flat = FlatTree::from(tree.clone());
+/// assert_eq!(flat.size(), 4);
+/// assert_eq!(flat.iter().collect::<Vec<_>>(), vec![&"root", &"a", &"b", &"c"]);
+/// assert_eq!(flat.children(0).collect::<Vec<_>>(), vec![1, 3]);
+/// assert_eq!(flat.first_child(1), Some(2));
+/// assert_eq!(flat.next_sibling(1), Some(3));
+/// assert_eq!(flat.label(3), Some(&"c"));
+///
+/// assert_eq!(Tree::from(flat), tree);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FlatTree<T> {
+    nodes: Vec<FlatNode<T>>
+}
+
+impl<T> FlatTree<T> {
+    /// Returns the total number of nodes in the tree, including the root.
+    pub fn size(&self) -> usize { self.nodes.len() }
+
+    pub fn label(&self, i: usize) -> Option<&T> { self.nodes.get(i).map(|n| &n.label) }
+    pub fn label_mut(&mut self, i: usize) -> Option<&mut T> { self.nodes.get_mut(i).map(|n| &mut n.label) }
+
+    pub fn first_child(&self, i: usize) -> Option<usize> { self.nodes.get(i)?.first_child }
+    pub fn next_sibling(&self, i: usize) -> Option<usize> { self.nodes.get(i)?.next_sibling }
+
+    /// Returns an iterator over the indices of the children of node `i`, in order.
+    pub fn children(&self, i: usize) -> Children<'_, T> {
+        Children { tree: self, next: self.first_child(i) }
+    }
+
+    /// Returns an iterator over the labels in depth-first pre-order.
+    pub fn iter(&self) -> Labels<'_, T> {
+        Labels { nodes: self.nodes.iter() }
+    }
+}
+
+impl<T> From<Tree<T>> for FlatTree<T> {
+    fn from(tree: Tree<T>) -> Self {
+        let mut nodes: Vec<FlatNode<T>> = Vec::new();
+        let mut last_child: Vec<Option<usize>> = Vec::new();
+
+        let mut stack: Vec<(Tree<T>, Option<usize>)> = vec![(tree, None)];
+        while let Some((tree, parent)) = stack.pop() {
+            let i = nodes.len();
+            if let Some(p) = parent {
+                match last_child[p] {
+                    Some(prev) => nodes[prev].next_sibling = Some(i),
+                    None => nodes[p].first_child = Some(i)
+                }
+                last_child[p] = Some(i);
+            }
+
+            let (label, children) = tree.into_parts();
+            nodes.push(FlatNode { label, first_child: None, next_sibling: None });
+            last_child.push(None);
+            stack.extend(children.into_iter().rev().map(|c| (c, Some(i))));
+        }
+
+        FlatTree { nodes }
+    }
+}
+
+impl<T> From<FlatTree<T>> for Tree<T> {
+    fn from(flat: FlatTree<T>) -> Self {
+        let counts: Vec<usize> = (0..flat.size()).map(|i| flat.children(i).count()).collect();
+
+        // Walking backwards, the children of a node are the topmost trees built so far.
+        let mut built: Vec<Tree<T>> = Vec::new();
+        for (node, count) in flat.nodes.into_iter().zip(counts).rev() {
+            let mut tree = Tree::new(node.label);
+            for _ in 0..count {
+                tree.attach_child(built.pop().expect("children are built before their parent"));
+            }
+            built.push(tree);
+        }
+
+        built.pop().expect("a flat tree always holds a root")
+    }
+}
+
+/// An iterator over the children of a node in a `FlatTree`.
+pub struct Children<'a, T> {
+    tree: &'a FlatTree<T>,
+    next: Option<usize>
+}
+
+impl<'a, T> Iterator for Children<'a, T> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let i = self.next?;
+        self.next = self.tree.next_sibling(i);
+        Some(i)
+    }
+}
+
+/// An iterator over the labels of a `FlatTree` in depth-first pre-order.
+pub struct Labels<'a, T> {
+    nodes: slice::Iter<'a, FlatNode<T>>
+}
+
+impl<'a, T> Iterator for Labels<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.nodes.next().map(|n| &n.label)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) { self.nodes.size_hint() }
+}
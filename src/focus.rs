@@ -1,19 +1,111 @@
+use alloc::{vec, vec::Vec, string::String};
+use alloc::collections::BTreeMap;
+use core::error::Error;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::mem;
+
+#[cfg(feature = "serde")]
+use core::convert::TryFrom;
+#[cfg(feature = "serde")]
 use serde::{Serialize, Deserialize};
 
+pub use super::tree::Path;
 use super::tree::Tree;
+use super::forest::Forest;
 
 /// A movement within a tree.
 /// * `Jump::Up` - To the parent of focused subtree.
 /// * `Jump::Down` - To the first child of focused subtree.
+/// * `Jump::DownLast` - To the last child of focused subtree.
+/// * `Jump::Child(n)` - To the child of focused subtree at index `n`.
 /// * `Jump::Lateral(n)` - To the sibling subtree of distance `n`.
+/// * `Jump::FirstSibling` - To the first sibling subtree.
+/// * `Jump::LastSibling` - To the last sibling subtree.
+/// * `Jump::Root` - To the root of the tree.
+/// * `Jump::ToPath(path)` - To the subtree at the absolute `path`.
+/// * `Jump::Next` - To the next subtree in depth-first pre-order.
+/// * `Jump::Prev` - To the previous subtree in depth-first pre-order.
+/// * `Jump::NextLeaf` - To the next leaf in depth-first pre-order.
+/// * `Jump::PrevLeaf` - To the previous leaf in depth-first pre-order.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Jump {
     Up,
     Down,
-    Lateral(i32)
+    DownLast,
+    Child(usize),
+    Lateral(i32),
+    FirstSibling,
+    LastSibling,
+    Root,
+    ToPath(Path),
+    Next,
+    Prev,
+    NextLeaf,
+    PrevLeaf
+}
+
+/// How a `Jump::Lateral` past the first or last sibling behaves.
+/// * `LateralPolicy::Saturate` (default) - Stops at the first or last sibling.
+/// * `LateralPolicy::Wrap` - Wraps around to the other end of the siblings.
+/// * `LateralPolicy::Fail` - Fails with `JumpError::NoSibling`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum LateralPolicy {
+    #[default]
+    Saturate,
+    Wrap,
+    Fail
+}
+
+impl LateralPolicy {
+    /// Returns the index of the sibling of distance `x` from index `o` among `ub` siblings.
+    pub(crate) fn apply(self, o: usize, x: i32, ub: usize) -> Option<usize> {
+        let (n, ub) = (o as i64 + x as i64, ub as i64);
+        match self {
+            LateralPolicy::Saturate => Some(n.clamp(0, ub - 1) as usize),
+            LateralPolicy::Wrap     => Some(n.rem_euclid(ub) as usize),
+            LateralPolicy::Fail     => if (0..ub).contains(&n) { Some(n as usize) } else { None }
+        }
+    }
+}
+
+/// The reason a `Jump` couldn't be made.
+/// * `JumpError::AtRoot` - The focus has no parent nor siblings.
+/// * `JumpError::AtLeaf` - The focused subtree has no children.
+/// * `JumpError::NoSibling` - There is no sibling in the direction of the jump.
+/// * `JumpError::InvalidPath` - The path doesn't point to an existing point on the tree.
+/// * `JumpError::AtEnd` - The focus is on the last subtree in depth-first pre-order.
+/// * `JumpError::AtStart` - The focus is on the first subtree in depth-first pre-order.
+/// * `JumpError::UnknownMark` - There is no mark of that name.
+/// * `JumpError::Diverged` - The jump leads apart in two trees traversed in lockstep.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum JumpError {
+    AtRoot,
+    AtLeaf,
+    NoSibling,
+    InvalidPath,
+    AtEnd,
+    AtStart,
+    UnknownMark,
+    Diverged
+}
+
+impl fmt::Display for JumpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            JumpError::AtRoot      => "the focus is at the root",
+            JumpError::AtLeaf      => "the focused subtree has no children",
+            JumpError::NoSibling   => "there is no sibling in that direction",
+            JumpError::InvalidPath => "the path doesn't point to an existing point on the tree",
+            JumpError::AtEnd       => "there is no next subtree",
+            JumpError::AtStart     => "there is no previous subtree",
+            JumpError::UnknownMark => "there is no mark of that name",
+            JumpError::Diverged    => "the jump leads apart in the two trees"
+        })
+    }
 }
 
-/// A path to a point within a tree.
-pub type Path = Vec<usize>;
+impl Error for JumpError {}
 
 /// A tool for building trees.
 /// # Example
@@ -27,28 +119,161 @@ pub type Path = Vec<usize>;
 /// assert_eq!(focus.focused().label(), &1);
 /// assert_eq!(focus.path().len(), 1);
 ///
-/// focus.jump(Jump::Up);
+/// focus.jump(Jump::Up).unwrap();
 /// assert_eq!(focus.focused().label(), &0);
 ///
 /// focus.create_subtree(2);
 /// assert_eq!(focus.focused().label(), &2);
 ///
-/// focus.jump(Jump::Lateral(-1));
+/// focus.jump(Jump::Lateral(-1)).unwrap();
 /// assert_eq!(focus.focused().label(), &1);
 ///
 /// focus.create_subtree(3);
 /// assert_eq!(focus.labels(), vec![&0, &1, &3]);
+///
+/// let snapshot = focus.clone();
+/// focus.jump_silent(Jump::Up);
+/// assert_ne!(focus, snapshot);
 /// ```
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(try_from = "FocusParts<T>"))]
 pub struct Focus<T> {
+    tree: Tree<T>,
+    path: Path,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    lateral: LateralPolicy,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    clipboard: Vec<Tree<T>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    marks: BTreeMap<String, Path>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    history: Option<History>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    journal: Option<Journal<T>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    log: Option<Log<T>>
+}
+
+/// The serialized fields of a `Focus`, checked before becoming one.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct FocusParts<T> {
     tree: Tree<T>,
     path: Path
 }
 
+#[cfg(feature = "serde")]
+impl<T> TryFrom<FocusParts<T>> for Focus<T> {
+    type Error = JumpError;
+
+    /// Fails with `JumpError::InvalidPath` if the path doesn't point to an existing point on the tree,
+    /// so that deserializing a stale focus is an error rather than a later panic.
+    /// # Example
+    /// ```
+    /// use serde::Deserialize;
+    /// use tt::focus::Focus;
+    /// use tt::tree::Tree;
+    ///
+    /// let document = |path| Tree::<String>::from_sexpr(&format!("(focus (tree (label (0)) (children)) (path {}))", path)).unwrap();
+    ///
+    /// assert!(Focus::<u64>::deserialize(document("").to_json_value()).is_ok());
+    /// assert!(Focus::<u64>::deserialize(document("(0 (0))").to_json_value()).is_err());
+    /// ```
+    fn try_from(parts: FocusParts<T>) -> Result<Self, JumpError> {
+        Focus::from(parts.tree, Some(parts.path)).ok_or(JumpError::InvalidPath)
+    }
+}
+
+/// The positions a `Focus` jumped away from, and back from.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+struct History {
+    back: Vec<Path>,
+    forward: Vec<Path>
+}
+
+/// A single change of a tree at an absolute path.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Edit<T> {
+    Insert(Path, Tree<T>),
+    Remove(Path),
+    Replace(Path, Tree<T>),
+    Label(Path, T)
+}
+
+/// The edits made by one editing method, reverted in reverse order,
+/// and the focus path to restore.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Step<T> {
+    path: Path,
+    edits: Vec<Edit<T>>
+}
+
+/// The clone function of a label type, letting edits be copied without a `Clone` bound.
+#[derive(Debug)]
+struct Cloner<T>(fn(&T) -> T);
+
+impl<T> Cloner<T> {
+    /// Returns a copy of `edit`.
+    fn copy(&self, edit: &Edit<T>) -> Edit<T> {
+        match edit {
+            Edit::Insert(at, tree)  => Edit::Insert(at.clone(), tree.map_ref(self.0)),
+            Edit::Remove(at)        => Edit::Remove(at.clone()),
+            Edit::Replace(at, tree) => Edit::Replace(at.clone(), tree.map_ref(self.0)),
+            Edit::Label(at, label)  => Edit::Label(at.clone(), (self.0)(label))
+        }
+    }
+}
+
+impl<T> Clone for Cloner<T> {
+    fn clone(&self) -> Self { Cloner(self.0) }
+}
+
+// There is only one meaningful clone function per label type, so all are considered equal.
+impl<T> PartialEq for Cloner<T> {
+    fn eq(&self, _: &Self) -> bool { true }
+}
+
+impl<T> Eq for Cloner<T> {}
+
+impl<T> Hash for Cloner<T> {
+    fn hash<H: Hasher>(&self, _: &mut H) {}
+}
+
+/// The undo and redo steps of a `Focus`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Journal<T> {
+    undo: Vec<Step<T>>,
+    redo: Vec<Step<T>>,
+    pending: Vec<Edit<T>>,
+    start: Path,
+    clone: Cloner<T>
+}
+
+impl<T> Journal<T> {
+    fn new(clone: fn(&T) -> T) -> Self {
+        Self {
+            undo: Vec::new(),
+            redo: Vec::new(),
+            pending: Vec::new(),
+            start: Path::new(),
+            clone: Cloner(clone)
+        }
+    }
+}
+
+/// The inverses of the edits made within a transaction, and the focus path to restore.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Log<T> {
+    path: Path,
+    edits: Vec<Edit<T>>,
+    clone: Cloner<T>
+}
+
 impl<T> Focus<T> {
     /// Constructs and returns a new `Focus` from provided `Tree`.
     /// * If `None` is provided as `path` - the focus will have an empty path.
     /// * If `Some(path)` is provided as `path` - the focus path will be `path`.
+    ///
     /// Returns none if the provided path doesn't point to an existing point on the tree.
     /// # Example
     /// ```
@@ -76,15 +301,19 @@ impl<T> Focus<T> {
     pub fn from(tree: Tree<T>, path: Option<Path>) -> Option<Self> {
         let focus = Self {
             tree,
-            path: match path {
-                Some(path) => path,
-                None       => Path::new()
-            }
+            path: path.unwrap_or_default(),
+            lateral: LateralPolicy::default(),
+            clipboard: Vec::new(),
+            marks: BTreeMap::new(),
+            history: None,
+            journal: None,
+            log: None
         };
 
-        match focus.at_path(&focus.path) {
-            Some(_) => Some(focus),
-            None    => None
+        if focus.tree.contains_path(&focus.path) {
+            Some(focus)
+        } else {
+            None
         }
     }
 
@@ -96,68 +325,2081 @@ impl<T> Focus<T> {
     /// Returns the path to the focused tree/subtree.
     pub fn path(&self) -> &Path { &self.path }
 
-    /// Returns the tree/subtree reached by `path`.
-    fn at_path(&self, path: &Path) -> Option<&Tree<T>> {
-        let mut cur = &self.tree;
-        for i in path.iter() {
-            match cur.child_at(*i) {
-                Some(child) => cur = child,
-                None => return None
-            };
-        }
+    /// Returns how lateral jumps past the first or last sibling behave.
+    pub fn lateral_policy(&self) -> LateralPolicy { self.lateral }
 
-        Some(cur)
+    /// Sets how lateral jumps past the first or last sibling behave.
+    pub fn set_lateral_policy(&mut self, policy: LateralPolicy) {
+        self.lateral = policy;
     }
 
+    /// Returns the whole tree, from its root.
+    pub fn tree(&self) -> &Tree<T> { &self.tree }
+
+    /// Consumes the focus and returns the whole tree, from its root.
+    /// # Example
+    /// ```
+    /// use tt::focus::Focus;
+    ///
+    /// let mut focus = Focus::new(0);
+    /// focus.create_subtree(1);
+    /// assert_eq!(focus.tree().label(), &0);
+    ///
+    /// let tree = focus.into_tree();
+    /// assert_eq!(tree.iter().collect::<Vec<_>>(), vec![&0, &1]);
+    /// ```
+    pub fn into_tree(self) -> Tree<T> { self.tree }
+
     /// Returns the currently focused tree/subtree.
+    /// Each call walks the path from the root, so reads in a loop should keep the returned reference.
     pub fn focused(&self) -> &Tree<T> {
-        self.at_path(&self.path).unwrap()
+        self.tree.get(&self.path).unwrap()
     }
 
-    /// Returns a mutable reference to the focused tree/subtree.
-    fn focused_mut(&mut self) -> &mut Tree<T> {
-        let mut cur = &mut self.tree;
-        for i in self.path.iter() {
-            cur = cur.child_at_mut(*i).unwrap();
+    /// Returns the parent of the focused subtree.
+    /// Returns none if the root is focused.
+    /// # Example
+    /// ```
+    /// use tt::focus::Focus;
+    ///
+    /// let mut focus = Focus::new(0);
+    /// assert!(focus.parent().is_none());
+    /// assert_eq!(focus.siblings().len(), 1);
+    ///
+    /// focus.create_subtree(1);
+    /// focus.insert_sibling_after(2);
+    /// focus.create_subtree(3);
+    /// assert_eq!(focus.parent().unwrap().label(), &2);
+    /// assert_eq!(focus.siblings().len(), 1);
+    /// assert_eq!(focus.ancestors().map(|tree| *tree.label()).collect::<Vec<_>>(), vec![2, 0]);
+    ///
+    /// focus.jump_to(&[1]).unwrap();
+    /// assert_eq!(focus.siblings().iter().map(|tree| *tree.label()).collect::<Vec<_>>(), vec![1, 2]);
+    /// ```
+    pub fn parent(&self) -> Option<&Tree<T>> {
+        let (_, parent) = self.path.split_last()?;
+        self.tree.get(parent)
+    }
+
+    /// Returns the focused subtree along with its siblings, in order.
+    /// The root is its only sibling.
+    pub fn siblings(&self) -> &[Tree<T>] {
+        match self.parent() {
+            Some(parent) => parent.children_iter().as_slice(),
+            None => core::slice::from_ref(&self.tree)
         }
+    }
 
-        cur
+    /// Returns an iterator over the ancestors of the focused subtree, from its parent up to the root.
+    pub fn ancestors(&self) -> Ancestors<'_, T> {
+        let mut trees = Vec::with_capacity(self.path.len());
+        if let Some((_, parent)) = self.path.split_last() {
+            let mut tree = &self.tree;
+            trees.push(tree);
+            for &i in parent {
+                tree = tree.child_at(i).unwrap();
+                trees.push(tree);
+            }
+        }
+        Ancestors { trees }
     }
 
     /// Changes focus according to the provided `jump`.
-    pub fn jump(&mut self, jump: Jump) {
-        match jump {
-            Jump::Up => { self.path.pop(); }
-            Jump::Down if self.focused().children() > 0 => { self.path.push(0) }
-            Jump::Lateral(x) if self.path.len() > 0 => {
-                let o = self.path.pop().unwrap() as i32;
-                let ub = self.focused().children() as i32;
+    /// A lateral jump past the first or last sibling behaves according to the `LateralPolicy`.
+    ///
+    /// Returns an error, leaving the focus unchanged, if the jump is impossible.
+    /// # Example
+    /// ```
+    /// use tt::focus::{Jump, JumpError, Focus};
+    ///
+    /// let mut focus = Focus::new(0);
+    /// assert_eq!(focus.jump(Jump::Up), Err(JumpError::AtRoot));
+    /// assert_eq!(focus.jump(Jump::Down), Err(JumpError::AtLeaf));
+    ///
+    /// focus.create_subtree(1);
+    /// assert_eq!(focus.jump(Jump::Lateral(1)), Err(JumpError::NoSibling));
+    /// assert_eq!(focus.jump(Jump::Up), Ok(()));
+    ///
+    /// focus.create_subtree(2);
+    /// assert_eq!(focus.jump(Jump::ToPath(vec![2])), Err(JumpError::InvalidPath));
+    /// focus.jump(Jump::FirstSibling).unwrap();
+    /// assert_eq!(focus.focused().label(), &1);
+    ///
+    /// focus.jump(Jump::Root).unwrap();
+    /// focus.jump(Jump::DownLast).unwrap();
+    /// assert_eq!(focus.focused().label(), &2);
+    /// ```
+    /// ```
+    /// use tt::tree::Tree;
+    /// use tt::focus::{Jump, JumpError, Focus};
+    ///
+    /// let mut tree = Tree::new(0);
+    /// tree.create_subtree(1);
+    /// tree.child_at_mut(0).unwrap().create_subtree(2);
+    /// tree.create_subtree(3);
+    ///
+    /// // Walking the tree in document order.
+    /// let mut focus = Focus::from(tree, None).unwrap();
+    /// let mut labels = vec![*focus.focused().label()];
+    /// while focus.jump(Jump::Next).is_ok() {
+    ///     labels.push(*focus.focused().label());
+    /// }
+    /// assert_eq!(labels, vec![0, 1, 2, 3]);
+    /// assert_eq!(focus.jump(Jump::Next), Err(JumpError::AtEnd));
+    ///
+    /// focus.jump(Jump::Prev).unwrap();
+    /// assert_eq!(focus.focused().label(), &2);
+    ///
+    /// // Skipping interior subtrees.
+    /// focus.jump(Jump::Root).unwrap();
+    /// focus.jump(Jump::NextLeaf).unwrap();
+    /// assert_eq!(focus.focused().label(), &2);
+    /// focus.jump(Jump::NextLeaf).unwrap();
+    /// assert_eq!(focus.focused().label(), &3);
+    /// assert_eq!(focus.jump(Jump::NextLeaf), Err(JumpError::AtEnd));
+    /// focus.jump(Jump::PrevLeaf).unwrap();
+    /// assert_eq!(focus.focused().label(), &2);
+    /// assert_eq!(focus.jump(Jump::PrevLeaf), Err(JumpError::AtStart));
+    /// ```
+    /// ```
+    /// use tt::focus::{Jump, JumpError, LateralPolicy, Focus};
+    ///
+    /// let mut focus = Focus::new(0);
+    /// for label in 1..4 {
+    ///     focus.create_subtree(label);
+    ///     focus.jump(Jump::Up).unwrap();
+    /// }
+    /// focus.jump(Jump::Down).unwrap();
+    ///
+    /// focus.jump(Jump::Lateral(-5)).unwrap_err();
+    /// focus.jump(Jump::Lateral(5)).unwrap();
+    /// assert_eq!(focus.focused().label(), &3);
+    ///
+    /// focus.set_lateral_policy(LateralPolicy::Wrap);
+    /// focus.jump(Jump::Lateral(1)).unwrap();
+    /// assert_eq!(focus.focused().label(), &1);
+    ///
+    /// focus.set_lateral_policy(LateralPolicy::Fail);
+    /// assert_eq!(focus.jump(Jump::Lateral(-1)), Err(JumpError::NoSibling));
+    /// focus.jump(Jump::Lateral(2)).unwrap();
+    /// assert_eq!(focus.focused().label(), &3);
+    /// ```
+    pub fn jump(&mut self, jump: Jump) -> Result<(), JumpError> {
+        let from = self.path.clone();
+        self.apply(jump)?;
+        self.visited(from);
+        Ok(())
+    }
+
+    fn apply(&mut self, jump: Jump) -> Result<(), JumpError> {
+        jump_on(&children_on(&self.tree), 0, &mut self.path, self.lateral, jump)
+    }
+
+    /// Changes focus according to the provided `jump`, ignoring impossible jumps.
+    pub fn jump_silent(&mut self, jump: Jump) {
+        let _ = self.jump(jump);
+    }
+
+    /// Moves the focus to the subtree at `path`.
+    /// Returns `JumpError::InvalidPath`, leaving the focus unchanged, if the path doesn't point
+    /// to an existing point on the tree.
+    /// # Example
+    /// ```
+    /// use tt::focus::{JumpError, Focus};
+    ///
+    /// let mut focus = Focus::new(0);
+    /// focus.create_subtree(1);
+    /// let saved = focus.path().clone();
+    ///
+    /// focus.jump_to(&[]).unwrap();
+    /// assert_eq!(focus.jump_to(&[0, 0]), Err(JumpError::InvalidPath));
+    /// focus.jump_to(&saved).unwrap();
+    /// assert_eq!(focus.focused().label(), &1);
+    /// ```
+    pub fn jump_to(&mut self, path: &[usize]) -> Result<(), JumpError> {
+        self.jump(Jump::ToPath(path.to_vec()))
+    }
+
+    /// Changes focus according to each of the provided `jumps` in order.
+    /// Returns the first error, restoring the focus to where it was, if any jump is impossible.
+    /// # Example
+    /// ```
+    /// use tt::focus::{Jump, JumpError, Focus};
+    ///
+    /// let mut focus = Focus::new(0);
+    /// focus.create_subtree(1);
+    /// focus.create_subtree(2);
+    ///
+    /// assert_eq!(focus.jump_all(vec![Jump::Up, Jump::Up, Jump::Up]), Err(JumpError::AtRoot));
+    /// assert_eq!(focus.focused().label(), &2);
+    ///
+    /// focus.jump_all(vec![Jump::Up, Jump::Up]).unwrap();
+    /// assert_eq!(focus.focused().label(), &0);
+    /// ```
+    pub fn jump_all<I: IntoIterator<Item = Jump>>(&mut self, jumps: I) -> Result<(), JumpError> {
+        let path = self.path.clone();
+        for jump in jumps {
+            if let Err(e) = self.apply(jump) {
+                self.path = path;
+                return Err(e);
+            }
+        }
+        self.visited(path);
+        Ok(())
+    }
+
+    /// Returns the path the focus would reach by each of `jumps` in order, without moving it.
+    pub(crate) fn resolve<I: IntoIterator<Item = Jump>>(&self, jumps: I) -> Result<Path, JumpError> {
+        let mut path = self.path.clone();
+        for jump in jumps {
+            jump_on(&children_on(&self.tree), 0, &mut path, self.lateral, jump)?;
+        }
+        Ok(path)
+    }
 
-                let n = if o + x < 0 { 0 } 
-                        else if o + x >= ub { (ub - 1) }
-                        else { o + x };
+    /// Moves the focus to the next subtree in depth-first pre-order whose label matches `predicate`.
+    /// Returns false, leaving the focus unchanged, if no later subtree matches.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    /// use tt::focus::Focus;
+    ///
+    /// let mut tree = Tree::new("todo");
+    /// tree.create_subtree("done");
+    /// tree.child_at_mut(0).unwrap().create_subtree("todo");
+    /// tree.create_subtree("todo");
+    ///
+    /// let mut focus = Focus::from(tree, None).unwrap();
+    /// assert!(focus.find_next(|label| *label == "todo"));
+    /// assert_eq!(focus.path(), &vec![0, 0]);
+    /// assert!(focus.find_next(|label| *label == "todo"));
+    /// assert_eq!(focus.path(), &vec![1]);
+    /// assert!(!focus.find_next(|label| *label == "todo"));
+    /// ```
+    pub fn find_next<P: FnMut(&T) -> bool>(&mut self, mut predicate: P) -> bool {
+        let tree = &self.tree;
+        let children = |p: &[usize]| tree.get(p).unwrap().children();
 
-                self.path.push(n as usize);
+        let mut path = self.path.clone();
+        while next(&mut path, &children) {
+            if predicate(tree.get(&path).unwrap().label()) {
+                let from = mem::replace(&mut self.path, path);
+                self.visited(from);
+                return true;
             }
-            _ => {}
         }
+        false
     }
 
     /// Creates new child subtree of focused tree/subtree.
     pub fn create_subtree(&mut self, label: T) {
-        self.focused_mut().create_subtree(label);
-        self.path.push(self.focused().children() - 1);
+        let i = self.focused().children();
+        self.graft_at(i, Tree::new(label));
     }
 
-    /// Get all labels along current path.
-    pub fn labels(&self) -> Vec<&T> {
-        let mut labels = Vec::new();
-        self.path.iter().fold(Vec::new(), |mut acc, x| {
-            labels.push(self.at_path(&acc).unwrap().label());
-            acc.push(*x);
-            acc
-        });
-        labels.push(self.focused().label());
-        labels
+    /// Moves the focused subtree to be the last child of the subtree at `dest`, keeping the focus on it.
+    /// `dest` is a path in the tree as it is before the move.
+    ///
+    /// Returns `JumpError::AtRoot` if the root is focused, or `JumpError::InvalidPath` if `dest`
+    /// doesn't point to an existing point on the tree or lies within the focused subtree,
+    /// leaving the tree unchanged.
+    /// # Example
+    /// ```
+    /// use tt::focus::{JumpError, Focus};
+    ///
+    /// let mut focus = Focus::new(0);
+    /// focus.create_subtree(1);
+    /// focus.jump_to(&[]).unwrap();
+    /// focus.create_subtree(2);
+    /// focus.create_subtree(3);
+    ///
+    /// // Move 1 under 2, shifting 2 into its place.
+    /// focus.jump_to(&[0]).unwrap();
+    /// assert_eq!(focus.move_focused_to(vec![0]), Err(JumpError::InvalidPath));
+    /// focus.move_focused_to(vec![1]).unwrap();
+    /// assert_eq!(focus.path(), &vec![0, 1]);
+    /// assert_eq!(focus.labels(), vec![&0, &2, &1]);
+    /// ```
+    pub fn move_focused_to(&mut self, mut dest: Path) -> Result<(), JumpError> {
+        let (&i, parent) = self.path.split_last().ok_or(JumpError::AtRoot)?;
+        if dest.starts_with(&self.path) || !self.tree.contains_path(&dest) {
+            return Err(JumpError::InvalidPath);
+        }
+
+        if dest.len() > parent.len() && dest.starts_with(parent) && dest[parent.len()] > i {
+            dest[parent.len()] -= 1;
+        }
+
+        let subtree = self.take_focused().unwrap();
+        self.path = dest;
+        let i = self.focused().children();
+        self.put(i, subtree);
+        self.seal();
+        Ok(())
+    }
+
+    /// Moves the focused subtree out of its parent, right after the parent, keeping the focus on it.
+    /// Returns false, leaving the tree unchanged, if the focus has no grandparent.
+    /// # Example
+    /// ```
+    /// use tt::focus::Focus;
+    ///
+    /// let mut focus = Focus::new(0);
+    /// focus.create_subtree(1);
+    /// assert!(!focus.promote());
+    ///
+    /// focus.create_subtree(2);
+    /// assert!(focus.promote());
+    /// assert_eq!(focus.path(), &vec![1]);
+    ///
+    /// assert!(focus.demote());
+    /// assert_eq!(focus.path(), &vec![0, 0]);
+    /// assert!(!focus.demote());
+    /// assert_eq!(focus.labels(), vec![&0, &1, &2]);
+    /// ```
+    pub fn promote(&mut self) -> bool {
+        if self.path.len() < 2 {
+            return false;
+        }
+
+        let subtree = self.take_focused().unwrap();
+        let j = self.path.pop().unwrap();
+        self.put(j + 1, subtree);
+        self.seal();
+        true
+    }
+
+    /// Moves the focused subtree to be the last child of its previous sibling, keeping the focus on it.
+    /// Returns false, leaving the tree unchanged, if the focus has no previous sibling.
+    pub fn demote(&mut self) -> bool {
+        match self.path.last().copied() {
+            Some(i) if i > 0 => {
+                let subtree = self.take_focused().unwrap();
+                self.path.push(i - 1);
+                let j = self.focused().children();
+                self.put(j, subtree);
+                self.seal();
+                true
+            }
+            _ => false
+        }
+    }
+
+    /// Returns the subtrees held by the clipboard.
+    pub fn clipboard(&self) -> &[Tree<T>] { &self.clipboard }
+
+    /// Replaces the subtrees held by the clipboard, returning the old ones.
+    pub fn set_clipboard(&mut self, trees: Vec<Tree<T>>) -> Vec<Tree<T>> {
+        mem::replace(&mut self.clipboard, trees)
+    }
+
+    /// Detaches the focused subtree into the clipboard, replacing its contents,
+    /// and moves the focus to the parent.
+    /// Returns false, leaving the tree unchanged, if the root is focused.
+    pub fn cut(&mut self) -> bool {
+        match self.detach() {
+            Some(subtree) => {
+                self.clipboard = vec![subtree];
+                true
+            }
+            None => false
+        }
+    }
+
+    /// Creates new sibling subtree with `label` right before the focused subtree, moving the focus to it.
+    /// Returns false, leaving the tree unchanged, if the root is focused.
+    /// # Example
+    /// ```
+    /// use tt::focus::Focus;
+    ///
+    /// let mut focus = Focus::new(0);
+    /// assert!(!focus.insert_sibling_before(1));
+    ///
+    /// focus.create_subtree(2);
+    /// assert!(focus.insert_sibling_before(1));
+    /// assert!(focus.insert_sibling_after(3));
+    /// assert_eq!(focus.path(), &vec![1]);
+    ///
+    /// focus.jump_to(&[]).unwrap();
+    /// assert_eq!(focus.focused().iter().collect::<Vec<_>>(), vec![&0, &1, &3, &2]);
+    /// ```
+    pub fn insert_sibling_before(&mut self, label: T) -> bool {
+        match self.path.last().copied() {
+            Some(i) => {
+                self.insert_sibling_at(i, Tree::new(label));
+                self.seal();
+                true
+            }
+            None => false
+        }
+    }
+
+    /// Creates new sibling subtree with `label` right after the focused subtree, moving the focus to it.
+    /// Returns false, leaving the tree unchanged, if the root is focused.
+    pub fn insert_sibling_after(&mut self, label: T) -> bool {
+        match self.path.last().copied() {
+            Some(i) => {
+                self.insert_sibling_at(i + 1, Tree::new(label));
+                self.seal();
+                true
+            }
+            None => false
+        }
+    }
+
+    fn insert_sibling_at(&mut self, i: usize, tree: Tree<T>) {
+        let mut at = self.path.clone();
+        *at.last_mut().unwrap() = i;
+        self.edit(Edit::Insert(at.clone(), tree));
+        self.path = at;
+    }
+
+    /// Attaches `tree` as the last child of focused tree/subtree, moving the focus into it.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    /// use tt::focus::{Jump, Focus};
+    ///
+    /// let mut focus = Focus::new(0);
+    /// let mut cut = Tree::new(1);
+    /// cut.create_subtree(2);
+    ///
+    /// focus.graft(cut);
+    /// assert_eq!(focus.path(), &vec![0]);
+    ///
+    /// focus.jump(Jump::Up).unwrap();
+    /// focus.graft_at(0, Tree::new(3));
+    /// assert_eq!(focus.labels(), vec![&0, &3]);
+    /// ```
+    pub fn graft(&mut self, tree: Tree<T>) {
+        let i = self.focused().children();
+        self.graft_at(i, tree);
+    }
+
+    /// Attaches `tree` as a child of focused tree/subtree at index `i`, moving the focus into it.
+    /// An index past the last child is clamped, appending the tree.
+    pub fn graft_at(&mut self, i: usize, tree: Tree<T>) {
+        self.put(i, tree);
+        self.seal();
+    }
+
+    /// Attaches `tree` as a child at index `i`, clamped, and moves the focus into it.
+    fn put(&mut self, i: usize, tree: Tree<T>) {
+        let mut at = self.path.clone();
+        at.push(i.min(self.focused().children()));
+        self.edit(Edit::Insert(at.clone(), tree));
+        self.path = at;
+    }
+
+    /// Replaces the label of the focused subtree, returning the old one.
+    /// # Example
+    /// ```
+    /// use tt::focus::Focus;
+    ///
+    /// let mut focus = Focus::new("draft");
+    /// focus.set_journal(true);
+    /// assert_eq!(focus.set_label("final"), "draft");
+    /// assert_eq!(focus.focused().label(), &"final");
+    ///
+    /// focus.undo();
+    /// assert_eq!(focus.focused().label(), &"draft");
+    /// ```
+    pub fn set_label(&mut self, label: T) -> T {
+        let old = match self.edit(Edit::Label(self.path.clone(), label)) {
+            Edit::Label(_, old) => old,
+            _ => unreachable!()
+        };
+        self.seal();
+        old
+    }
+
+    /// Returns a mutable reference to the label of the focused subtree.
+    /// Changes made through it are neither journaled nor reverted by a failed transaction.
+    pub fn label_mut(&mut self) -> &mut T {
+        self.tree.get_mut(&self.path).unwrap().label_mut()
+    }
+
+    /// Runs `f` on the focused subtree, returning its result.
+    /// Afterwards, marks and history within the subtree that no longer point to
+    /// an existing point on the tree are dropped. The changes are journaled,
+    /// and reverted by a failed transaction, as one replacement of the subtree.
+    /// # Example
+    /// ```
+    /// use tt::focus::{JumpError, Focus};
+    ///
+    /// let mut focus = Focus::new(0);
+    /// focus.create_subtree(1);
+    /// focus.create_subtree(2);
+    /// focus.set_mark("leaf");
+    /// focus.jump_to(&[0]).unwrap();
+    /// focus.set_journal(true);
+    ///
+    /// let removed = focus.with_focused_mut(|tree| tree.remove_child(0));
+    /// assert_eq!(removed.unwrap().label(), &2);
+    /// assert!(focus.focused().is_leaf());
+    /// assert_eq!(focus.jump_to_mark("leaf"), Err(JumpError::UnknownMark));
+    ///
+    /// focus.undo();
+    /// assert_eq!(focus.focused().children(), 1);
+    /// ```
+    pub fn with_focused_mut<R, F: FnOnce(&mut Tree<T>) -> R>(&mut self, f: F) -> R {
+        let clone = match (&self.log, &self.journal) {
+            (Some(log), _)     => Some(log.clone.clone()),
+            (_, Some(journal)) => Some(journal.clone.clone()),
+            _                  => None
+        };
+        let inverse = clone.map(|clone| Edit::Replace(self.path.clone(), self.focused().map_ref(clone.0)));
+
+        let result = f(self.tree.get_mut(&self.path).unwrap());
+        self.revalidated();
+
+        if let Some(inverse) = inverse {
+            self.logged(&inverse);
+            self.journaled(&inverse);
+            self.seal();
+        }
+        result
+    }
+
+    /// Replaces the focused subtree with `tree`, returning the old one.
+    /// The focus stays on the same path, now on `tree`.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    /// use tt::focus::Focus;
+    ///
+    /// let mut focus = Focus::new(0);
+    /// focus.create_subtree(1);
+    ///
+    /// let mut tree = Tree::new(2);
+    /// tree.create_subtree(3);
+    /// assert_eq!(focus.replace_focused(tree).label(), &1);
+    /// assert_eq!(focus.labels(), vec![&0, &2]);
+    /// assert_eq!(focus.focused().children(), 1);
+    /// ```
+    pub fn replace_focused(&mut self, tree: Tree<T>) -> Tree<T> {
+        let old = match self.edit(Edit::Replace(self.path.clone(), tree)) {
+            Edit::Replace(_, old) => old,
+            _ => unreachable!()
+        };
+        self.seal();
+        old
+    }
+
+    /// Removes and returns the focused subtree, moving the focus to its previous sibling,
+    /// or to its parent if it was the first child.
+    /// Returns none if the root is focused.
+    /// # Example
+    /// ```
+    /// use tt::focus::Focus;
+    ///
+    /// let mut focus = Focus::new(0);
+    /// assert!(focus.delete_focused().is_none());
+    ///
+    /// focus.create_subtree(1);
+    /// focus.jump_to(&[]).unwrap();
+    /// focus.create_subtree(2);
+    ///
+    /// assert_eq!(focus.delete_focused().unwrap().label(), &2);
+    /// assert_eq!(focus.focused().label(), &1);
+    /// assert_eq!(focus.delete_focused().unwrap().label(), &1);
+    /// assert_eq!(focus.focused().label(), &0);
+    /// assert!(focus.focused().is_leaf());
+    /// ```
+    pub fn delete_focused(&mut self) -> Option<Tree<T>> {
+        let i = *self.path.last()?;
+        let removed = self.take_focused();
+        if i > 0 {
+            self.path.push(i - 1);
+        }
+        self.seal();
+        removed
+    }
+
+    /// Removes and returns the focused subtree, moving the focus to its parent.
+    /// Returns none if the root is focused.
+    /// # Example
+    /// ```
+    /// use tt::focus::Focus;
+    ///
+    /// let mut focus = Focus::new(0);
+    /// focus.create_subtree(1);
+    /// focus.create_subtree(2);
+    ///
+    /// let cut = focus.detach().unwrap();
+    /// assert_eq!(cut.label(), &2);
+    /// assert_eq!(focus.focused().label(), &1);
+    /// assert!(focus.focused().is_leaf());
+    /// ```
+    pub fn detach(&mut self) -> Option<Tree<T>> {
+        let removed = self.take_focused();
+        self.seal();
+        removed
+    }
+
+    /// Removes and returns the focused subtree, moving the focus to its parent.
+    fn take_focused(&mut self) -> Option<Tree<T>> {
+        if self.path.is_empty() {
+            return None;
+        }
+
+        let removed = match self.edit(Edit::Remove(self.path.clone())) {
+            Edit::Insert(_, tree) => tree,
+            _ => unreachable!()
+        };
+        self.path.pop();
+        Some(removed)
+    }
+
+    /// Inserts `tree` at the absolute path `at`, which may lie anywhere on the tree.
+    /// The focus, marks and history keep pointing to the same subtrees, shifting past `tree` as needed.
+    /// An index past the last child is clamped, appending the tree.
+    ///
+    /// Returns `JumpError::AtRoot` if `at` is empty, or `JumpError::InvalidPath` if its parent
+    /// doesn't point to an existing point on the tree, leaving the tree unchanged.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    /// use tt::focus::{JumpError, Focus};
+    ///
+    /// let mut focus = Focus::new(0);
+    /// focus.create_subtree(1);
+    ///
+    /// focus.insert_at(vec![0], Tree::new(2)).unwrap();
+    /// assert_eq!(focus.path(), &vec![1]);
+    /// assert_eq!(focus.focused().label(), &1);
+    /// assert_eq!(focus.insert_at(vec![3, 0], Tree::new(3)), Err(JumpError::InvalidPath));
+    /// ```
+    pub fn insert_at(&mut self, mut at: Path, tree: Tree<T>) -> Result<(), JumpError> {
+        let (&i, parent) = at.split_last().ok_or(JumpError::AtRoot)?;
+        let children = self.tree.get(parent).ok_or(JumpError::InvalidPath)?.children();
+        *at.last_mut().unwrap() = i.min(children);
+
+        let mut path = self.path.clone();
+        shift_inserted(&mut path, &at);
+        self.edit(Edit::Insert(at, tree));
+        self.path = path;
+        self.seal();
+        Ok(())
+    }
+
+    /// Removes and returns the subtree at the absolute path `at`, which may lie anywhere on the tree.
+    /// The focus, marks and history keep pointing to the same subtrees, shifting back as needed.
+    /// If the focus was within the removed subtree, it moves to the previous sibling of that subtree,
+    /// or to its parent if it was the first child.
+    ///
+    /// Returns none if `at` is empty or doesn't point to an existing point on the tree.
+    /// # Example
+    /// ```
+    /// use tt::focus::Focus;
+    ///
+    /// let mut focus = Focus::new(0);
+    /// focus.create_subtree(1);
+    /// focus.jump_to(&[]).unwrap();
+    /// focus.create_subtree(2);
+    /// focus.create_subtree(3);
+    ///
+    /// // Removing an earlier sibling shifts the focus back.
+    /// assert_eq!(focus.remove_at(&[0]).unwrap().label(), &1);
+    /// assert_eq!(focus.path(), &vec![0, 0]);
+    ///
+    /// // Removing an ancestor moves the focus out of it.
+    /// assert_eq!(focus.remove_at(&[0]).unwrap().label(), &2);
+    /// assert!(focus.path().is_empty());
+    /// assert!(focus.remove_at(&[0]).is_none());
+    /// ```
+    pub fn remove_at(&mut self, at: &[usize]) -> Option<Tree<T>> {
+        if at.is_empty() || !self.tree.contains_path(at) {
+            return None;
+        }
+
+        let mut path = self.path.clone();
+        if !shift_removed(&mut path, at) {
+            path.truncate(at.len() - 1);
+            if let Some(i) = at.last().copied().filter(|&i| i > 0) {
+                path.push(i - 1);
+            }
+        }
+
+        let removed = match self.edit(Edit::Remove(at.to_vec())) {
+            Edit::Insert(_, tree) => tree,
+            _ => unreachable!()
+        };
+        self.path = path;
+        self.seal();
+        Some(removed)
+    }
+
+    /// Marks the focused subtree as `name`, replacing any previous mark of that name.
+    /// Marks follow their subtrees as siblings are inserted or removed,
+    /// and are dropped along with their subtrees.
+    /// # Example
+    /// ```
+    /// use tt::focus::{JumpError, Focus};
+    ///
+    /// let mut focus = Focus::new(0);
+    /// focus.create_subtree(1);
+    /// focus.set_mark("a");
+    ///
+    /// focus.insert_sibling_before(2);
+    /// focus.jump_to_mark("a").unwrap();
+    /// assert_eq!(focus.focused().label(), &1);
+    /// assert_eq!(focus.mark("a"), Some(&vec![1]));
+    ///
+    /// focus.delete_focused();
+    /// assert_eq!(focus.jump_to_mark("a"), Err(JumpError::UnknownMark));
+    /// ```
+    pub fn set_mark<S: Into<String>>(&mut self, name: S) {
+        self.marks.insert(name.into(), self.path.clone());
+    }
+
+    /// Returns the path of the subtree marked as `name`.
+    pub fn mark(&self, name: &str) -> Option<&Path> { self.marks.get(name) }
+
+    /// Removes the mark `name`, returning its path.
+    pub fn remove_mark(&mut self, name: &str) -> Option<Path> { self.marks.remove(name) }
+
+    /// Moves the focus to the subtree marked as `name`.
+    /// Returns `JumpError::UnknownMark`, leaving the focus unchanged, if there is no such mark.
+    pub fn jump_to_mark(&mut self, name: &str) -> Result<(), JumpError> {
+        let path = self.marks.get(name).ok_or(JumpError::UnknownMark)?.clone();
+        let from = mem::replace(&mut self.path, path);
+        self.visited(from);
+        Ok(())
+    }
+
+    /// Sets whether jumps are recorded, so that `back` and `forward` can retrace them.
+    /// Turning recording off forgets the recorded history.
+    /// # Example
+    /// ```
+    /// use tt::focus::{Jump, Focus};
+    ///
+    /// let mut focus = Focus::new(0);
+    /// focus.create_subtree(1);
+    /// focus.create_subtree(2);
+    /// focus.set_history(true);
+    ///
+    /// focus.jump(Jump::Root).unwrap();
+    /// focus.jump(Jump::Down).unwrap();
+    /// assert!(focus.back());
+    /// assert!(focus.back());
+    /// assert_eq!(focus.focused().label(), &2);
+    /// assert!(!focus.back());
+    ///
+    /// assert!(focus.forward());
+    /// assert!(focus.path().is_empty());
+    /// ```
+    pub fn set_history(&mut self, record: bool) {
+        self.history = if record { Some(History::default()) } else { None };
+    }
+
+    /// Returns whether jumps are recorded.
+    pub fn records_history(&self) -> bool { self.history.is_some() }
+
+    /// Moves the focus back to where it was before the last recorded jump.
+    /// Returns false if there is nowhere to go back to.
+    pub fn back(&mut self) -> bool {
+        let history = match &mut self.history {
+            Some(history) => history,
+            None => return false
+        };
+
+        match history.back.pop() {
+            Some(path) => {
+                history.forward.push(mem::replace(&mut self.path, path));
+                true
+            }
+            None => false
+        }
+    }
+
+    /// Moves the focus forward again to where it was before the last `back`.
+    /// Returns false if there is nowhere to go forward to.
+    pub fn forward(&mut self) -> bool {
+        let history = match &mut self.history {
+            Some(history) => history,
+            None => return false
+        };
+
+        match history.forward.pop() {
+            Some(path) => {
+                history.back.push(mem::replace(&mut self.path, path));
+                true
+            }
+            None => false
+        }
+    }
+
+    /// Records that the focus moved away from `from`.
+    fn visited(&mut self, from: Path) {
+        if let Some(history) = &mut self.history {
+            if from != self.path {
+                history.back.push(from);
+                history.forward.clear();
+            }
+        }
+    }
+
+    /// Returns whether edits are journaled.
+    pub fn records_journal(&self) -> bool { self.journal.is_some() }
+
+    /// Reverts the last journaled edit, restoring the focus to where it was before it.
+    /// Returns false if there is nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        match self.journal.as_mut().and_then(|journal| journal.undo.pop()) {
+            Some(step) => {
+                let step = self.revert(step);
+                self.journal.as_mut().unwrap().redo.push(step);
+                true
+            }
+            None => false
+        }
+    }
+
+    /// Reapplies the last undone edit, restoring the focus to where it was after it.
+    /// Returns false if there is nothing to redo.
+    pub fn redo(&mut self) -> bool {
+        match self.journal.as_mut().and_then(|journal| journal.redo.pop()) {
+            Some(step) => {
+                let step = self.revert(step);
+                self.journal.as_mut().unwrap().undo.push(step);
+                true
+            }
+            None => false
+        }
+    }
+
+    /// Applies the edits of `step` in reverse, returning the step reverting that.
+    fn revert(&mut self, step: Step<T>) -> Step<T> {
+        let edits = step.edits.into_iter().rev().map(|edit| self.perform(edit)).collect();
+        let path = mem::replace(&mut self.path, step.path);
+        Step { path, edits }
+    }
+
+    /// Applies `edit` like `perform`, adding its inverse to the journal.
+    fn edit(&mut self, edit: Edit<T>) -> Edit<T> {
+        let inverse = self.perform(edit);
+        self.journaled(&inverse);
+        inverse
+    }
+
+    /// Adds a copy of `inverse` to the pending journal step.
+    fn journaled(&mut self, inverse: &Edit<T>) {
+        if let Some(journal) = &mut self.journal {
+            if journal.pending.is_empty() {
+                journal.start = self.path.clone();
+            }
+            let copy = journal.clone.copy(inverse);
+            journal.pending.push(copy);
+        }
+    }
+
+    /// Ends the journal step of the current editing method.
+    fn seal(&mut self) {
+        if let Some(journal) = &mut self.journal {
+            if !journal.pending.is_empty() {
+                let edits = mem::take(&mut journal.pending);
+                journal.undo.push(Step { path: mem::take(&mut journal.start), edits });
+                journal.redo.clear();
+            }
+        }
+    }
+
+    /// Applies `edit` to the tree, keeping marks and history in step, and returns its inverse.
+    fn perform(&mut self, edit: Edit<T>) -> Edit<T> {
+        let inverse = self.perform_unlogged(edit);
+        self.logged(&inverse);
+        inverse
+    }
+
+    /// Adds a copy of `inverse` to the log of the running transaction.
+    fn logged(&mut self, inverse: &Edit<T>) {
+        if let Some(log) = &mut self.log {
+            let copy = log.clone.copy(inverse);
+            log.edits.push(copy);
+        }
+    }
+
+    fn perform_unlogged(&mut self, edit: Edit<T>) -> Edit<T> {
+        match edit {
+            Edit::Insert(at, tree) => {
+                let (&i, parent) = at.split_last().unwrap();
+                self.tree.get_mut(parent).unwrap().insert_tree_at(i, tree);
+                self.inserted(&at);
+                Edit::Remove(at)
+            }
+            Edit::Remove(at) => {
+                let (&i, parent) = at.split_last().unwrap();
+                let tree = self.tree.get_mut(parent).unwrap().remove_child(i).unwrap();
+                self.removed(&at);
+                Edit::Insert(at, tree)
+            }
+            Edit::Replace(at, tree) => {
+                let old = mem::replace(self.tree.get_mut(&at).unwrap(), tree);
+                self.replaced(&at);
+                Edit::Replace(at, old)
+            }
+            Edit::Label(at, label) => {
+                let old = self.tree.get_mut(&at).unwrap().set_label(label);
+                Edit::Label(at, old)
+            }
+        }
+    }
+
+    /// Shifts the marks and history after a subtree was inserted at `at`.
+    fn inserted(&mut self, at: &[usize]) {
+        for mark in self.marks.values_mut() {
+            shift_inserted(mark, at);
+        }
+        if let Some(history) = &mut self.history {
+            for path in history.back.iter_mut().chain(&mut history.forward) {
+                shift_inserted(path, at);
+            }
+        }
+    }
+
+    /// Shifts the marks and history after the subtree at `at` was removed, dropping those within it.
+    fn removed(&mut self, at: &[usize]) {
+        self.marks.retain(|_, mark| shift_removed(mark, at));
+        if let Some(history) = &mut self.history {
+            history.back.retain_mut(|path| shift_removed(path, at));
+            history.forward.retain_mut(|path| shift_removed(path, at));
+        }
+    }
+
+    /// Drops the marks and history within the subtree at `at` after it was replaced.
+    fn replaced(&mut self, at: &[usize]) {
+        let within = |path: &Path| path.len() > at.len() && path.starts_with(at);
+        self.marks.retain(|_, mark| !within(mark));
+        if let Some(history) = &mut self.history {
+            history.back.retain(|path| !within(path));
+            history.forward.retain(|path| !within(path));
+        }
+    }
+
+    /// Drops the marks and history that no longer point to an existing point on the tree.
+    fn revalidated(&mut self) {
+        let tree = &self.tree;
+        self.marks.retain(|_, mark| tree.contains_path(mark));
+        if let Some(history) = &mut self.history {
+            history.back.retain(|path| tree.contains_path(path));
+            history.forward.retain(|path| tree.contains_path(path));
+        }
+    }
+
+    /// Get all labels along current path.
+    pub fn labels(&self) -> Vec<&T> {
+        let mut labels = Vec::new();
+        self.path.iter().fold(Vec::new(), |mut acc, x| {
+            labels.push(self.tree.get(&acc).unwrap().label());
+            acc.push(*x);
+            acc
+        });
+        labels.push(self.focused().label());
+        labels
+    }
+
+    /// Get mutable references to all labels along current path.
+    /// Changes made through them are neither journaled nor reverted by a failed transaction.
+    /// # Example
+    /// ```
+    /// use tt::focus::Focus;
+    ///
+    /// let mut focus = Focus::new((0, false));
+    /// focus.create_subtree((1, false));
+    /// focus.create_subtree((2, false));
+    ///
+    /// for label in focus.labels_mut() {
+    ///     label.1 = true;
+    /// }
+    /// assert_eq!(focus.labels(), vec![&(0, true), &(1, true), &(2, true)]);
+    /// ```
+    pub fn labels_mut(&mut self) -> Vec<&mut T> {
+        let mut labels = Vec::with_capacity(self.path.len() + 1);
+        let mut tree = &mut self.tree;
+        for &i in &self.path {
+            let (label, children) = tree.split_mut();
+            labels.push(label);
+            tree = &mut children[i];
+        }
+        labels.push(tree.label_mut());
+        labels
+    }
+}
+
+impl<T: Clone> Focus<T> {
+    /// Runs `f` on the focus as one transaction: if `f` returns an error,
+    /// every edit it made is reverted and the focus path restored.
+    /// A successful transaction is journaled as a single step, its edits not being journaled individually.
+    /// # Example
+    /// ```
+    /// use tt::focus::{JumpError, Focus};
+    ///
+    /// let mut focus = Focus::new(0);
+    /// focus.create_subtree(1);
+    ///
+    /// let result = focus.transaction(|focus| {
+    ///     focus.create_subtree(2);
+    ///     focus.create_subtree(3);
+    ///     focus.move_focused_to(vec![0, 0, 0, 5])
+    /// });
+    /// assert_eq!(result, Err(JumpError::InvalidPath));
+    /// assert_eq!(focus.path(), &vec![0]);
+    /// assert!(focus.focused().is_leaf());
+    ///
+    /// focus.set_journal(true);
+    /// focus.transaction(|focus| {
+    ///     focus.create_subtree(2);
+    ///     focus.insert_sibling_after(3);
+    ///     Ok::<_, JumpError>(())
+    /// }).unwrap();
+    /// assert_eq!(focus.focused().label(), &3);
+    /// assert!(focus.undo());
+    /// assert!(focus.focused().is_leaf());
+    /// ```
+    pub fn transaction<R, E, F: FnOnce(&mut Self) -> Result<R, E>>(&mut self, f: F) -> Result<R, E> {
+        self.seal();
+        let journal = self.journal.take();
+        let log = Log { path: self.path.clone(), edits: Vec::new(), clone: Cloner(T::clone) };
+        let outer = self.log.replace(log);
+
+        let result = f(self);
+
+        let inner = mem::replace(&mut self.log, outer).unwrap();
+        self.journal = journal;
+        match result {
+            Ok(_) => {
+                if let Some(outer) = &mut self.log {
+                    outer.edits.extend(inner.edits.iter().map(|edit| inner.clone.copy(edit)));
+                }
+                if let (Some(journal), false) = (&mut self.journal, inner.edits.is_empty()) {
+                    journal.undo.push(Step { path: inner.path, edits: inner.edits });
+                    journal.redo.clear();
+                }
+            }
+            Err(_) => {
+                for edit in inner.edits.into_iter().rev() {
+                    self.perform_unlogged(edit);
+                }
+                self.path = inner.path;
+            }
+        }
+        result
+    }
+
+    /// Sets whether edits are journaled, so that `undo` and `redo` can revert and reapply them.
+    /// Each editing method is one step, restoring the focus along with the tree.
+    /// Turning journaling off forgets the journal.
+    /// # Example
+    /// ```
+    /// use tt::focus::Focus;
+    ///
+    /// let mut focus = Focus::new(0);
+    /// focus.set_journal(true);
+    /// focus.create_subtree(1);
+    /// focus.create_subtree(2);
+    /// assert!(focus.promote());
+    ///
+    /// assert!(focus.undo());
+    /// assert_eq!(focus.labels(), vec![&0, &1, &2]);
+    /// assert!(focus.undo());
+    /// assert!(focus.undo());
+    /// assert!(!focus.undo());
+    /// assert!(focus.focused().is_leaf());
+    ///
+    /// assert!(focus.redo());
+    /// assert_eq!(focus.labels(), vec![&0, &1]);
+    /// ```
+    pub fn set_journal(&mut self, record: bool) {
+        self.journal = if record { Some(Journal::new(T::clone)) } else { None };
+    }
+
+    /// Copies the focused subtree into the clipboard, replacing its contents.
+    /// # Example
+    /// ```
+    /// use tt::focus::Focus;
+    ///
+    /// let mut focus = Focus::new(0);
+    /// focus.create_subtree(1);
+    /// assert!(!focus.paste_as_sibling());
+    ///
+    /// // Paste a copy of 1 under itself, and next to itself.
+    /// focus.copy();
+    /// assert!(focus.paste_as_child());
+    /// assert_eq!(focus.path(), &vec![0, 0]);
+    /// focus.jump_to(&[0]).unwrap();
+    /// assert!(focus.paste_as_sibling());
+    /// assert_eq!(focus.path(), &vec![1]);
+    ///
+    /// // Cut the pasted sibling back out.
+    /// assert!(focus.cut());
+    /// assert!(focus.path().is_empty());
+    /// assert_eq!(focus.clipboard().len(), 1);
+    /// assert_eq!(focus.focused().iter().collect::<Vec<_>>(), vec![&0, &1, &1]);
+    /// ```
+    pub fn copy(&mut self) {
+        self.clipboard = vec![self.focused().clone()];
+    }
+
+    /// Attaches copies of the clipboard subtrees as the last children of the focused subtree,
+    /// moving the focus to the last of them.
+    /// Returns false, leaving the tree unchanged, if the clipboard is empty.
+    pub fn paste_as_child(&mut self) -> bool {
+        if self.clipboard.is_empty() {
+            return false;
+        }
+
+        for subtree in self.clipboard.clone() {
+            let mut at = self.path.clone();
+            at.push(self.focused().children());
+            self.edit(Edit::Insert(at, subtree));
+        }
+        self.path.push(self.focused().children() - 1);
+        self.seal();
+        true
+    }
+
+    /// Inserts copies of the clipboard subtrees right after the focused subtree,
+    /// moving the focus to the last of them.
+    /// Returns false, leaving the tree unchanged, if the clipboard is empty or the root is focused.
+    pub fn paste_as_sibling(&mut self) -> bool {
+        if self.clipboard.is_empty() || self.path.is_empty() {
+            return false;
+        }
+
+        for subtree in self.clipboard.clone() {
+            let i = self.path.last().unwrap() + 1;
+            self.insert_sibling_at(i, subtree);
+        }
+        self.seal();
+        true
+    }
+
+    /// Inserts a copy of the focused subtree as its next sibling, moving the focus to the copy.
+    /// Returns false, leaving the tree unchanged, if the root is focused.
+    /// # Example
+    /// ```
+    /// use tt::focus::Focus;
+    ///
+    /// let mut focus = Focus::new(0);
+    /// assert!(!focus.duplicate_focused());
+    ///
+    /// focus.create_subtree(1);
+    /// focus.create_subtree(2);
+    /// focus.jump_to(&[0]).unwrap();
+    /// assert!(focus.duplicate_focused());
+    /// assert_eq!(focus.path(), &vec![1]);
+    /// assert_eq!(focus.focused().iter().collect::<Vec<_>>(), vec![&1, &2]);
+    /// ```
+    pub fn duplicate_focused(&mut self) -> bool {
+        match self.path.last().copied() {
+            Some(i) => {
+                let copy = self.focused().clone();
+                self.insert_sibling_at(i + 1, copy);
+                self.seal();
+                true
+            }
+            None => false
+        }
+    }
+}
+
+/// An iterator over the ancestors of the focused subtree of a `Focus`, from its parent up to the root.
+pub struct Ancestors<'a, T> {
+    trees: Vec<&'a Tree<T>>
+}
+
+impl<'a, T> Iterator for Ancestors<'a, T> {
+    type Item = &'a Tree<T>;
+
+    fn next(&mut self) -> Option<Self::Item> { self.trees.pop() }
+
+    fn size_hint(&self) -> (usize, Option<usize>) { (self.trees.len(), Some(self.trees.len())) }
+}
+
+/// Moves `path` according to `jump`, `children` counting the children at a path, lateral jumps
+/// behaving according to `lateral` and `path` staying no shorter than `min`.
+/// Returns an error, leaving `path` unchanged, if the jump is impossible.
+fn jump_on(children: &dyn Fn(&[usize]) -> usize, min: usize, path: &mut Path, lateral: LateralPolicy, jump: Jump) -> Result<(), JumpError> {
+    let siblings = |p: &Path| p.split_last().map_or(1, |(_, parent)| children(parent));
+    match jump {
+        Jump::Up if path.len() <= min => return Err(JumpError::AtRoot),
+        Jump::Up => { path.pop(); }
+        Jump::Down if children(path) == 0 => return Err(JumpError::AtLeaf),
+        Jump::Down => path.push(0),
+        Jump::DownLast if children(path) == 0 => return Err(JumpError::AtLeaf),
+        Jump::DownLast => path.push(children(path) - 1),
+        Jump::Child(_) if children(path) == 0 => return Err(JumpError::AtLeaf),
+        Jump::Child(i) if i < children(path) => path.push(i),
+        Jump::Child(_) => return Err(JumpError::InvalidPath),
+        Jump::Lateral(_) | Jump::FirstSibling | Jump::LastSibling if path.is_empty() => {
+            return Err(JumpError::AtRoot)
+        }
+        Jump::Lateral(x) => {
+            let o = *path.last().unwrap();
+            match lateral.apply(o, x, siblings(path)) {
+                Some(n) if n != o || x == 0 => *path.last_mut().unwrap() = n,
+                _ => return Err(JumpError::NoSibling)
+            }
+        }
+        Jump::FirstSibling => *path.last_mut().unwrap() = 0,
+        Jump::LastSibling => *path.last_mut().unwrap() = siblings(path) - 1,
+        Jump::Root => path.truncate(min),
+        Jump::ToPath(to) if to.len() >= min && to.iter().enumerate().all(|(k, &i)| i < children(&to[..k])) => *path = to,
+        Jump::ToPath(_) => return Err(JumpError::InvalidPath),
+        Jump::Next => if !next(path, children) { return Err(JumpError::AtEnd) },
+        Jump::Prev => if !prev(path, min, children) { return Err(JumpError::AtStart) },
+        Jump::NextLeaf => if !next_leaf(path, children) { return Err(JumpError::AtEnd) },
+        Jump::PrevLeaf => if !prev_leaf(path, min, children) { return Err(JumpError::AtStart) }
+    }
+    Ok(())
+}
+
+/// Returns a function counting the children of the subtree of `tree` at a path.
+fn children_on<T>(tree: &Tree<T>) -> impl Fn(&[usize]) -> usize + '_ {
+    move |p| tree.get(p).unwrap().children()
+}
+
+/// Returns the amount of children of the subtree of `forest` at `path`, the forest itself being at the empty path.
+fn children_in<T>(forest: &Forest<T>, path: &[usize]) -> usize {
+    match path {
+        [] => forest.trees(),
+        _ => forest.get(path).unwrap().children()
+    }
+}
+
+/// Shifts `path` for a subtree inserted at `at`, moving later siblings of `at` along.
+fn shift_inserted(path: &mut Path, at: &[usize]) {
+    if let Some((&i, parent)) = at.split_last() {
+        let k = parent.len();
+        if path.len() > k && path.starts_with(parent) && path[k] >= i {
+            path[k] += 1;
+        }
+    }
+}
+
+/// Shifts `path` for the subtree at `at` being removed, moving later siblings of `at` back.
+/// Returns false if `path` points within the removed subtree.
+fn shift_removed(path: &mut Path, at: &[usize]) -> bool {
+    if path.starts_with(at) {
+        return false;
+    }
+
+    if let Some((&i, parent)) = at.split_last() {
+        let k = parent.len();
+        if path.len() > k && path.starts_with(parent) && path[k] > i {
+            path[k] -= 1;
+        }
+    }
+    true
+}
+
+/// Moves `path` to the next point in depth-first pre-order, `children` counting the children at a path.
+/// Returns false, leaving `path` unchanged, if there is no next point.
+fn next(path: &mut Path, children: &dyn Fn(&[usize]) -> usize) -> bool {
+    if children(path) > 0 {
+        path.push(0);
+        return true;
+    }
+
+    for depth in (1..=path.len()).rev() {
+        let i = path[depth - 1];
+        if i + 1 < children(&path[..depth - 1]) {
+            path.truncate(depth);
+            path[depth - 1] = i + 1;
+            return true;
+        }
+    }
+    false
+}
+
+/// Moves `path` to the previous point in depth-first pre-order, no shorter than `min`,
+/// `children` counting the children at a path.
+/// Returns false, leaving `path` unchanged, if there is no previous point.
+fn prev(path: &mut Path, min: usize, children: &dyn Fn(&[usize]) -> usize) -> bool {
+    match path.last().copied() {
+        Some(i) if i > 0 => {
+            *path.last_mut().unwrap() = i - 1;
+            loop {
+                match children(path) {
+                    0 => return true,
+                    n => path.push(n - 1)
+                }
+            }
+        }
+        Some(_) if path.len() > min => {
+            path.pop();
+            true
+        }
+        _ => false
+    }
+}
+
+/// Moves `path` to the next leaf in depth-first pre-order, `children` counting the children at a path.
+/// Returns false, leaving `path` unchanged, if there is no next leaf.
+fn next_leaf(path: &mut Path, children: &dyn Fn(&[usize]) -> usize) -> bool {
+    let mut leaf = path.clone();
+    while next(&mut leaf, children) {
+        if children(&leaf) == 0 {
+            *path = leaf;
+            return true;
+        }
+    }
+    false
+}
+
+/// Moves `path` to the previous leaf in depth-first pre-order, no shorter than `min`,
+/// `children` counting the children at a path.
+/// Returns false, leaving `path` unchanged, if there is no previous leaf.
+fn prev_leaf(path: &mut Path, min: usize, children: &dyn Fn(&[usize]) -> usize) -> bool {
+    let mut leaf = path.clone();
+    while prev(&mut leaf, min, children) {
+        if children(&leaf) == 0 {
+            *path = leaf;
+            return true;
+        }
+    }
+    false
+}
+
+/// A tool for building and navigating forests, whose path starts with the index of a tree.
+/// Jumping laterally from a root moves between the trees of the forest.
+/// # Example
+/// ```
+/// use tt::focus::{Jump, ForestFocus};
+///
+/// let mut focus = ForestFocus::new(0);
+/// focus.create_subtree(1);
+/// focus.jump(Jump::Up).unwrap();
+/// focus.create_tree(2);
+/// assert_eq!(focus.path(), &vec![1]);
+///
+/// focus.jump(Jump::Lateral(-1)).unwrap();
+/// focus.jump(Jump::Down).unwrap();
+/// assert_eq!(focus.labels(), vec![&0, &1]);
+///
+/// // Document order crosses from one tree to the next.
+/// focus.jump(Jump::Next).unwrap();
+/// assert_eq!(focus.focused().label(), &2);
+/// focus.jump(Jump::Prev).unwrap();
+/// assert_eq!(focus.path(), &vec![0, 0]);
+///
+/// // The roots are the top of the forest.
+/// focus.jump(Jump::Up).unwrap();
+/// assert!(focus.jump(Jump::Up).is_err());
+/// assert_eq!(focus.focused().label(), &0);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(try_from = "ForestFocusParts<T>"))]
+pub struct ForestFocus<T> {
+    forest: Forest<T>,
+    path: Path,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    lateral: LateralPolicy
+}
+
+/// The serialized fields of a `ForestFocus`, checked before becoming one.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct ForestFocusParts<T> {
+    forest: Forest<T>,
+    path: Path
+}
+
+#[cfg(feature = "serde")]
+impl<T> TryFrom<ForestFocusParts<T>> for ForestFocus<T> {
+    type Error = JumpError;
+
+    /// Fails with `JumpError::InvalidPath` if the path doesn't point to an existing point on the forest.
+    fn try_from(parts: ForestFocusParts<T>) -> Result<Self, JumpError> {
+        ForestFocus::from(parts.forest, Some(parts.path)).ok_or(JumpError::InvalidPath)
+    }
+}
+
+impl<T> ForestFocus<T> {
+    /// Constructs and returns a new `ForestFocus` from provided `Forest`.
+    /// * If `None` is provided as `path` - the focus will be on the first tree.
+    /// * If `Some(path)` is provided as `path` - the focus path will be `path`.
+    ///
+    /// Returns none if the provided path doesn't point to an existing point on the forest.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    /// use tt::forest::Forest;
+    /// use tt::focus::ForestFocus;
+    ///
+    /// assert!(ForestFocus::from(Forest::<i32>::new(), None).is_none());
+    ///
+    /// let forest = Forest::from(vec![Tree::new(0), Tree::new(1)]);
+    /// let focus = ForestFocus::from(forest, Some(vec![1])).unwrap();
+    /// assert_eq!(focus.focused().label(), &1);
+    /// ```
+    pub fn from(forest: Forest<T>, path: Option<Path>) -> Option<Self> {
+        let focus = Self {
+            forest,
+            path: path.unwrap_or_else(|| vec![0]),
+            lateral: LateralPolicy::default()
+        };
+
+        if focus.forest.get(&focus.path).is_some() {
+            Some(focus)
+        } else {
+            None
+        }
+    }
+
+    /// Constructs and returns a new `ForestFocus` on a forest of a single tree with `label`.
+    pub fn new(label: T) -> Self {
+        Self::from(Forest::from(Tree::new(label)), None).unwrap()
+    }
+
+    /// Returns the path to the focused subtree, starting with the index of its tree.
+    pub fn path(&self) -> &Path { &self.path }
+
+    /// Returns how lateral jumps past the first or last sibling behave.
+    pub fn lateral_policy(&self) -> LateralPolicy { self.lateral }
+
+    /// Sets how lateral jumps past the first or last sibling behave.
+    pub fn set_lateral_policy(&mut self, policy: LateralPolicy) {
+        self.lateral = policy;
+    }
+
+    /// Returns the currently focused subtree.
+    pub fn focused(&self) -> &Tree<T> {
+        self.forest.get(&self.path).unwrap()
+    }
+
+    /// Returns a mutable reference to the focused subtree.
+    fn focused_mut(&mut self) -> &mut Tree<T> {
+        self.forest.get_mut(&self.path).unwrap()
+    }
+
+    /// Changes focus according to the provided `jump`.
+    /// `Jump::Root` goes to the root of the focused tree.
+    ///
+    /// Returns an error, leaving the focus unchanged, if the jump is impossible.
+    pub fn jump(&mut self, jump: Jump) -> Result<(), JumpError> {
+        let forest = &self.forest;
+        jump_on(&|p| children_in(forest, p), 1, &mut self.path, self.lateral, jump)
+    }
+
+    /// Changes focus according to the provided `jump`, ignoring impossible jumps.
+    pub fn jump_silent(&mut self, jump: Jump) {
+        let _ = self.jump(jump);
+    }
+
+    /// Moves the focus to the subtree at `path`.
+    /// Returns `JumpError::InvalidPath`, leaving the focus unchanged, if the path doesn't point
+    /// to an existing point on the forest.
+    pub fn jump_to(&mut self, path: &[usize]) -> Result<(), JumpError> {
+        self.jump(Jump::ToPath(path.to_vec()))
+    }
+
+    /// Changes focus according to each of the provided `jumps` in order.
+    /// Returns the first error, restoring the focus to where it was, if any jump is impossible.
+    pub fn jump_all<I: IntoIterator<Item = Jump>>(&mut self, jumps: I) -> Result<(), JumpError> {
+        let path = self.path.clone();
+        for jump in jumps {
+            if let Err(e) = self.jump(jump) {
+                self.path = path;
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    /// Creates new child subtree of focused subtree.
+    pub fn create_subtree(&mut self, label: T) {
+        self.focused_mut().create_subtree(label);
+        self.path.push(self.focused().children() - 1);
+    }
+
+    /// Creates new tree with `label` right after the focused tree, focusing it.
+    pub fn create_tree(&mut self, label: T) {
+        let i = self.path[0] + 1;
+        self.forest.insert_tree_at(i, Tree::new(label));
+        self.path = vec![i];
+    }
+
+    /// Get all labels along current path.
+    pub fn labels(&self) -> Vec<&T> {
+        (1..=self.path.len()).map(|n| self.forest.get(&self.path[..n]).unwrap().label()).collect()
+    }
+}
+
+/// A tool for editing and navigating a tree borrowed for the duration of an editing session,
+/// leaving the tree with its owner once the cursor is dropped.
+/// # Example
+/// ```
+/// use tt::tree::Tree;
+/// use tt::focus::{Jump, FocusMut};
+///
+/// let mut tree = Tree::new(0);
+/// {
+///     let mut focus = FocusMut::new(&mut tree);
+///     focus.create_subtree(1);
+///     focus.create_subtree(2);
+///     focus.jump(Jump::Up).unwrap();
+///     focus.insert_sibling_after(3);
+///     assert_eq!(focus.labels(), vec![&0, &3]);
+/// }
+/// assert_eq!(tree.iter().collect::<Vec<_>>(), vec![&0, &1, &2, &3]);
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct FocusMut<'a, T> {
+    tree: &'a mut Tree<T>,
+    path: Path,
+    lateral: LateralPolicy
+}
+
+impl<'a, T> FocusMut<'a, T> {
+    /// Constructs and returns a new `FocusMut` borrowing `tree`.
+    /// * If `None` is provided as `path` - the focus will have an empty path.
+    /// * If `Some(path)` is provided as `path` - the focus path will be `path`.
+    ///
+    /// Returns none if the provided path doesn't point to an existing point on the tree.
+    pub fn from(tree: &'a mut Tree<T>, path: Option<Path>) -> Option<Self> {
+        let path = path.unwrap_or_default();
+        if tree.contains_path(&path) {
+            Some(Self { tree, path, lateral: LateralPolicy::default() })
+        } else {
+            None
+        }
+    }
+
+    /// Constructs and returns a new `FocusMut` borrowing `tree`, focused on its root.
+    pub fn new(tree: &'a mut Tree<T>) -> Self {
+        Self::from(tree, None).unwrap()
+    }
+
+    /// Returns the path to the focused tree/subtree.
+    pub fn path(&self) -> &Path { &self.path }
+
+    /// Returns how lateral jumps past the first or last sibling behave.
+    pub fn lateral_policy(&self) -> LateralPolicy { self.lateral }
+
+    /// Sets how lateral jumps past the first or last sibling behave.
+    pub fn set_lateral_policy(&mut self, policy: LateralPolicy) {
+        self.lateral = policy;
+    }
+
+    /// Returns the whole tree, from its root.
+    pub fn tree(&self) -> &Tree<T> { self.tree }
+
+    /// Returns the currently focused tree/subtree.
+    pub fn focused(&self) -> &Tree<T> {
+        self.tree.get(&self.path).unwrap()
+    }
+
+    /// Returns a mutable reference to the focused tree/subtree.
+    fn focused_mut(&mut self) -> &mut Tree<T> {
+        self.tree.get_mut(&self.path).unwrap()
+    }
+
+    /// Returns a mutable reference to the label of the focused tree/subtree.
+    pub fn label_mut(&mut self) -> &mut T {
+        self.focused_mut().label_mut()
+    }
+
+    /// Changes focus according to the provided `jump`.
+    /// A lateral jump past the first or last sibling behaves according to the `LateralPolicy`.
+    ///
+    /// Returns an error, leaving the focus unchanged, if the jump is impossible.
+    pub fn jump(&mut self, jump: Jump) -> Result<(), JumpError> {
+        jump_on(&children_on(self.tree), 0, &mut self.path, self.lateral, jump)
+    }
+
+    /// Changes focus according to the provided `jump`, ignoring impossible jumps.
+    pub fn jump_silent(&mut self, jump: Jump) {
+        let _ = self.jump(jump);
+    }
+
+    /// Moves the focus to the subtree at `path`.
+    /// Returns `JumpError::InvalidPath`, leaving the focus unchanged, if the path doesn't point
+    /// to an existing point on the tree.
+    pub fn jump_to(&mut self, path: &[usize]) -> Result<(), JumpError> {
+        self.jump(Jump::ToPath(path.to_vec()))
+    }
+
+    /// Changes focus according to each of the provided `jumps` in order.
+    /// Returns the first error, restoring the focus to where it was, if any jump is impossible.
+    pub fn jump_all<I: IntoIterator<Item = Jump>>(&mut self, jumps: I) -> Result<(), JumpError> {
+        let path = self.path.clone();
+        for jump in jumps {
+            if let Err(e) = self.jump(jump) {
+                self.path = path;
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    /// Creates new child subtree of focused tree/subtree, moving the focus into it.
+    pub fn create_subtree(&mut self, label: T) {
+        self.graft(Tree::new(label));
+    }
+
+    /// Attaches `tree` as the last child of focused tree/subtree, moving the focus into it.
+    pub fn graft(&mut self, tree: Tree<T>) {
+        let i = self.focused().children();
+        self.graft_at(i, tree);
+    }
+
+    /// Attaches `tree` as a child of focused tree/subtree at index `i`, moving the focus into it.
+    /// An index past the last child is clamped, appending the tree.
+    pub fn graft_at(&mut self, i: usize, tree: Tree<T>) {
+        let i = i.min(self.focused().children());
+        self.focused_mut().insert_tree_at(i, tree);
+        self.path.push(i);
+    }
+
+    /// Creates new sibling subtree with `label` right before the focused subtree, moving the focus to it.
+    /// Returns false, leaving the tree unchanged, if the root is focused.
+    pub fn insert_sibling_before(&mut self, label: T) -> bool {
+        match self.path.last().copied() {
+            Some(i) => {
+                self.insert_sibling_at(i, Tree::new(label));
+                true
+            }
+            None => false
+        }
+    }
+
+    /// Creates new sibling subtree with `label` right after the focused subtree, moving the focus to it.
+    /// Returns false, leaving the tree unchanged, if the root is focused.
+    pub fn insert_sibling_after(&mut self, label: T) -> bool {
+        match self.path.last().copied() {
+            Some(i) => {
+                self.insert_sibling_at(i + 1, Tree::new(label));
+                true
+            }
+            None => false
+        }
+    }
+
+    fn insert_sibling_at(&mut self, i: usize, tree: Tree<T>) {
+        self.path.pop();
+        self.graft_at(i, tree);
+    }
+
+    /// Replaces the label of the focused subtree, returning the old one.
+    pub fn set_label(&mut self, label: T) -> T {
+        self.focused_mut().set_label(label)
+    }
+
+    /// Replaces the focused subtree with `tree`, returning the old one.
+    /// The focus stays on the same path, now on `tree`.
+    pub fn replace_focused(&mut self, tree: Tree<T>) -> Tree<T> {
+        mem::replace(self.focused_mut(), tree)
+    }
+
+    /// Removes and returns the focused subtree, moving the focus to its previous sibling,
+    /// or to its parent if it was the first child.
+    /// Returns none if the root is focused.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    /// use tt::focus::FocusMut;
+    ///
+    /// let mut tree = Tree::new(0);
+    /// tree.create_subtree(1);
+    /// tree.create_subtree(2);
+    ///
+    /// let mut focus = FocusMut::from(&mut tree, Some(vec![1])).unwrap();
+    /// assert_eq!(focus.delete_focused().unwrap().label(), &2);
+    /// assert_eq!(focus.focused().label(), &1);
+    /// assert_eq!(focus.detach().unwrap().label(), &1);
+    /// assert!(focus.detach().is_none());
+    /// assert!(tree.is_leaf());
+    /// ```
+    pub fn delete_focused(&mut self) -> Option<Tree<T>> {
+        let i = *self.path.last()?;
+        let removed = self.detach();
+        if i > 0 {
+            self.path.push(i - 1);
+        }
+        removed
+    }
+
+    /// Removes and returns the focused subtree, moving the focus to its parent.
+    /// Returns none if the root is focused.
+    pub fn detach(&mut self) -> Option<Tree<T>> {
+        let i = self.path.pop()?;
+        self.focused_mut().remove_child(i)
+    }
+
+    /// Get all labels along current path.
+    pub fn labels(&self) -> Vec<&T> {
+        (0..=self.path.len()).map(|n| self.tree.get(&self.path[..n]).unwrap().label()).collect()
+    }
+}
+
+/// A tool for editing a tree at several focus points at once, such as a multi-selection.
+/// Edits made at one focus point keep every other one pointing to the same subtree,
+/// and focus points meeting at the same subtree merge into one.
+/// # Example
+/// ```
+/// use tt::tree::Tree;
+/// use tt::focus::{Jump, MultiFocus};
+///
+/// let mut tree = Tree::new("root");
+/// tree.create_subtree("a");
+/// tree.child_at_mut(0).unwrap().create_subtree("b");
+/// tree.create_subtree("c");
+///
+/// let mut focus = MultiFocus::from(tree, vec![vec![0, 0], vec![1]]).unwrap();
+/// focus.set_label_all("done");
+/// assert_eq!(focus.tree().iter().collect::<Vec<_>>(), vec![&"root", &"a", &"done", &"done"]);
+///
+/// // Deleting the first focused subtree shifts the others back.
+/// focus.add_cursor(vec![0]).unwrap();
+/// assert_eq!(focus.delete_focused(2).unwrap().label(), &"a");
+/// assert_eq!(focus.paths(), &[vec![], vec![0]]);
+///
+/// assert_eq!(focus.jump_each(Jump::Down), 1);
+/// assert_eq!(focus.paths(), &[vec![0]]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(try_from = "MultiFocusParts<T>"))]
+pub struct MultiFocus<T> {
+    tree: Tree<T>,
+    paths: Vec<Path>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    lateral: LateralPolicy
+}
+
+/// The serialized fields of a `MultiFocus`, checked before becoming one.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct MultiFocusParts<T> {
+    tree: Tree<T>,
+    paths: Vec<Path>
+}
+
+#[cfg(feature = "serde")]
+impl<T> TryFrom<MultiFocusParts<T>> for MultiFocus<T> {
+    type Error = JumpError;
+
+    /// Fails with `JumpError::InvalidPath` if any of the paths doesn't point to an existing point on the tree.
+    fn try_from(parts: MultiFocusParts<T>) -> Result<Self, JumpError> {
+        MultiFocus::from(parts.tree, parts.paths).ok_or(JumpError::InvalidPath)
+    }
+}
+
+impl<T> MultiFocus<T> {
+    /// Constructs and returns a new `MultiFocus` from provided `Tree`, focused at each of `paths`.
+    /// Repeated paths are kept only once.
+    ///
+    /// Returns none if any of the paths doesn't point to an existing point on the tree.
+    pub fn from(tree: Tree<T>, paths: Vec<Path>) -> Option<Self> {
+        if !paths.iter().all(|path| tree.contains_path(path)) {
+            return None;
+        }
+
+        let mut focus = Self { tree, paths, lateral: LateralPolicy::default() };
+        focus.merge();
+        Some(focus)
+    }
+
+    /// Constructs and returns a new `MultiFocus` from provided `label`, focused at the root.
+    pub fn new(label: T) -> Self {
+        Self::from(Tree::new(label), vec![Path::new()]).unwrap()
+    }
+
+    /// Returns the paths of the focus points, in the order they were added.
+    pub fn paths(&self) -> &[Path] { &self.paths }
+    /// Returns how many focus points there are.
+    pub fn cursors(&self) -> usize { self.paths.len() }
+
+    /// Returns how lateral jumps past the first or last sibling behave.
+    pub fn lateral_policy(&self) -> LateralPolicy { self.lateral }
+
+    /// Sets how lateral jumps past the first or last sibling behave.
+    pub fn set_lateral_policy(&mut self, policy: LateralPolicy) {
+        self.lateral = policy;
+    }
+
+    /// Returns the whole tree, from its root.
+    pub fn tree(&self) -> &Tree<T> { &self.tree }
+
+    /// Consumes the focus and returns the whole tree, from its root.
+    pub fn into_tree(self) -> Tree<T> { self.tree }
+
+    /// Returns the subtree focused by the `i`th focus point.
+    pub fn focused(&self, i: usize) -> Option<&Tree<T>> {
+        self.tree.get(self.paths.get(i)?)
+    }
+
+    /// Returns the focused subtrees, in the order of the focus points.
+    pub fn focused_all(&self) -> Vec<&Tree<T>> {
+        self.paths.iter().map(|path| self.tree.get(path).unwrap()).collect()
+    }
+
+    /// Adds a focus point at `path`, unless there already is one.
+    /// Returns `JumpError::InvalidPath` if the path doesn't point to an existing point on the tree.
+    pub fn add_cursor(&mut self, path: Path) -> Result<(), JumpError> {
+        if !self.tree.contains_path(&path) {
+            return Err(JumpError::InvalidPath);
+        }
+        if !self.paths.contains(&path) {
+            self.paths.push(path);
+        }
+        Ok(())
+    }
+
+    /// Removes the `i`th focus point, returning its path.
+    pub fn remove_cursor(&mut self, i: usize) -> Option<Path> {
+        if i < self.paths.len() {
+            Some(self.paths.remove(i))
+        } else {
+            None
+        }
+    }
+
+    /// Moves the `i`th focus point according to the provided `jump`.
+    /// Returns an error, leaving the focus point unchanged, if the jump is impossible
+    /// or `JumpError::InvalidPath` if there is no such focus point.
+    pub fn jump(&mut self, i: usize, jump: Jump) -> Result<(), JumpError> {
+        let path = self.paths.get_mut(i).ok_or(JumpError::InvalidPath)?;
+        jump_on(&children_on(&self.tree), 0, path, self.lateral, jump)?;
+        self.merge();
+        Ok(())
+    }
+
+    /// Moves every focus point according to the provided `jump`, leaving those that can't make it in place.
+    /// Returns how many focus points moved.
+    pub fn jump_each(&mut self, jump: Jump) -> usize {
+        let (tree, lateral) = (&self.tree, self.lateral);
+        let moved = self.paths.iter_mut()
+            .map(|path| jump_on(&children_on(tree), 0, path, lateral, jump.clone()).is_ok())
+            .filter(|&moved| moved)
+            .count();
+        self.merge();
+        moved
+    }
+
+    /// Creates new child subtree with `label` of the subtree focused by the `i`th focus point, moving it into it.
+    /// Returns false, leaving the tree unchanged, if there is no such focus point.
+    pub fn create_subtree(&mut self, i: usize, label: T) -> bool {
+        let mut at = match self.paths.get(i) {
+            Some(path) => path.clone(),
+            None => return false
+        };
+        at.push(self.tree.get(&at).unwrap().children());
+
+        self.insert(&at, Tree::new(label));
+        self.paths[i] = at;
+        true
+    }
+
+    /// Removes and returns the subtree focused by the `i`th focus point, moving it to its previous sibling,
+    /// or to its parent if it was the first child.
+    /// Focus points within the removed subtree move along with it.
+    /// Returns none if the focus point is on the root or there is no such focus point.
+    pub fn delete_focused(&mut self, i: usize) -> Option<Tree<T>> {
+        let at = self.paths.get(i).filter(|path| !path.is_empty())?.clone();
+        let removed = self.remove(&at);
+        self.merge();
+        Some(removed)
+    }
+
+    /// Removes and returns every focused subtree, in depth-first pre-order, moving each focus point
+    /// to the parent of its subtree. Subtrees within other focused subtrees are removed along with them,
+    /// and a focus point on the root stays in place.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    /// use tt::focus::MultiFocus;
+    ///
+    /// let mut tree = Tree::new(0);
+    /// tree.create_subtree(1);
+    /// tree.child_at_mut(0).unwrap().create_subtree(2);
+    /// tree.create_subtree(3);
+    ///
+    /// let mut focus = MultiFocus::from(tree, vec![vec![1], vec![0, 0], vec![0]]).unwrap();
+    /// let removed = focus.delete_all();
+    /// assert_eq!(removed.iter().map(|tree| tree.size()).collect::<Vec<_>>(), vec![2, 1]);
+    /// assert_eq!(focus.paths(), &[vec![]]);
+    /// assert!(focus.tree().is_leaf());
+    /// ```
+    pub fn delete_all(&mut self) -> Vec<Tree<T>> {
+        let mut targets: Vec<Path> = self.paths.iter().filter(|path| !path.is_empty()).cloned().collect();
+        targets.sort();
+        targets.dedup_by(|inner, outer| inner.starts_with(outer));
+
+        // Removing the later subtrees first leaves the paths of the earlier ones intact.
+        let mut removed: Vec<Tree<T>> = targets.iter().rev().map(|at| {
+            let (&i, parent) = at.split_last().unwrap();
+            for path in &mut self.paths {
+                if !shift_removed(path, at) {
+                    path.truncate(parent.len());
+                }
+            }
+            self.tree.get_mut(parent).unwrap().remove_child(i).unwrap()
+        }).collect();
+        self.merge();
+
+        removed.reverse();
+        removed
+    }
+
+    /// Removes and returns the subtree at `at`, shifting the focus points back.
+    /// Focus points within it move to its previous sibling, or to its parent if it was the first child.
+    fn remove(&mut self, at: &[usize]) -> Tree<T> {
+        let (&i, parent) = at.split_last().unwrap();
+        let removed = self.tree.get_mut(parent).unwrap().remove_child(i).unwrap();
+        for path in &mut self.paths {
+            if !shift_removed(path, at) {
+                path.truncate(parent.len());
+                if i > 0 {
+                    path.push(i - 1);
+                }
+            }
+        }
+        removed
+    }
+
+    /// Inserts `tree` at `at`, shifting the focus points along.
+    fn insert(&mut self, at: &[usize], tree: Tree<T>) {
+        let (&i, parent) = at.split_last().unwrap();
+        self.tree.get_mut(parent).unwrap().insert_tree_at(i, tree);
+        for path in &mut self.paths {
+            shift_inserted(path, at);
+        }
+    }
+
+    /// Merges the focus points on the same subtree, keeping the first of them.
+    fn merge(&mut self) {
+        let mut seen: Vec<Path> = Vec::new();
+        self.paths.retain(|path| {
+            let first = !seen.contains(path);
+            if first {
+                seen.push(path.clone());
+            }
+            first
+        });
+    }
+}
+
+impl<T: Clone> MultiFocus<T> {
+    /// Replaces the label of every focused subtree with a copy of `label`.
+    pub fn set_label_all(&mut self, label: T) {
+        for path in &self.paths {
+            self.tree.get_mut(path).unwrap().set_label(label.clone());
+        }
+    }
+}
+
+/// A tool for navigating two trees in lockstep, such as two versions of one document,
+/// keeping a single path valid in both.
+/// # Example
+/// ```
+/// use tt::tree::Tree;
+/// use tt::focus::{Jump, JumpError, CoFocus};
+///
+/// let mut before = Tree::new("root");
+/// before.create_subtree("a");
+/// before.create_subtree("b");
+///
+/// let mut after = before.clone();
+/// after.child_at_mut(1).unwrap().create_subtree("c");
+///
+/// let mut focus = CoFocus::new(&before, &after);
+/// focus.jump(Jump::Down).unwrap();
+/// assert_eq!(focus.focused().0.label(), &"a");
+///
+/// focus.jump(Jump::Next).unwrap();
+/// assert!(focus.diverges());
+/// assert_eq!(focus.jump(Jump::Next), Err(JumpError::Diverged));
+/// assert_eq!(focus.path(), &vec![1]);
+///
+/// focus.jump(Jump::Root).unwrap();
+/// assert!(focus.next_divergence());
+/// assert_eq!(focus.path(), &vec![1]);
+/// assert!(!focus.next_divergence());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CoFocus<'a, T, U> {
+    left: &'a Tree<T>,
+    right: &'a Tree<U>,
+    path: Path,
+    lateral: LateralPolicy
+}
+
+impl<'a, T, U> CoFocus<'a, T, U> {
+    /// Constructs and returns a new `CoFocus` on the roots of `left` and `right`.
+    pub fn new(left: &'a Tree<T>, right: &'a Tree<U>) -> Self {
+        Self { left, right, path: Path::new(), lateral: LateralPolicy::default() }
+    }
+
+    /// Returns the path to the focused subtrees.
+    pub fn path(&self) -> &Path { &self.path }
+
+    /// Returns how lateral jumps past the first or last sibling behave.
+    pub fn lateral_policy(&self) -> LateralPolicy { self.lateral }
+
+    /// Sets how lateral jumps past the first or last sibling behave.
+    pub fn set_lateral_policy(&mut self, policy: LateralPolicy) {
+        self.lateral = policy;
+    }
+
+    /// Returns the currently focused subtrees of the left and right trees.
+    pub fn focused(&self) -> (&'a Tree<T>, &'a Tree<U>) {
+        (self.left.get(&self.path).unwrap(), self.right.get(&self.path).unwrap())
+    }
+
+    /// Returns whether the focused subtrees have different amounts of children.
+    pub fn diverges(&self) -> bool {
+        let (left, right) = self.focused();
+        left.children() != right.children()
+    }
+
+    /// Changes focus in both trees according to the provided `jump`.
+    ///
+    /// Returns `JumpError::Diverged` if the jump is possible in only one of the trees
+    /// or leads to different paths in them, or the error of the left tree if it's impossible in both,
+    /// leaving the focus unchanged.
+    pub fn jump(&mut self, jump: Jump) -> Result<(), JumpError> {
+        let (mut left, mut right) = (self.path.clone(), self.path.clone());
+        let lr = jump_on(&children_on(self.left), 0, &mut left, self.lateral, jump.clone());
+        let rr = jump_on(&children_on(self.right), 0, &mut right, self.lateral, jump);
+        match (lr, rr) {
+            (Ok(()), Ok(())) if left == right => {
+                self.path = left;
+                Ok(())
+            }
+            (Err(e), Err(_)) => Err(e),
+            _ => Err(JumpError::Diverged)
+        }
+    }
+
+    /// Changes focus in both trees according to the provided `jump`, ignoring impossible jumps.
+    pub fn jump_silent(&mut self, jump: Jump) {
+        let _ = self.jump(jump);
+    }
+
+    /// Moves the focus to the next subtrees in depth-first pre-order, over the points both trees share,
+    /// that have different amounts of children.
+    /// Returns false, leaving the focus unchanged, if there are no such subtrees.
+    pub fn next_divergence(&mut self) -> bool {
+        let (left, right) = (self.left, self.right);
+        let children = |p: &[usize]| left.get(p).unwrap().children().min(right.get(p).unwrap().children());
+
+        let mut path = self.path.clone();
+        while next(&mut path, &children) {
+            if left.get(&path).unwrap().children() != right.get(&path).unwrap().children() {
+                self.path = path;
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Get the labels of both trees along current path.
+    pub fn labels(&self) -> Vec<(&'a T, &'a U)> {
+        (0..=self.path.len()).map(|n| {
+            let path = &self.path[..n];
+            (self.left.get(path).unwrap().label(), self.right.get(path).unwrap().label())
+        }).collect()
     }
 }
@@ -0,0 +1,162 @@
+use alloc::{vec, vec::Vec};
+use core::slice;
+
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
+use super::iter;
+use super::tree::Tree;
+
+/// An ordered collection of trees, for documents with several top-level items.
+/// (De)serializes as a plain sequence of trees.
+/// # Example
+/// ```
+/// use tt::tree::Tree;
+/// use tt::forest::Forest;
+///
+/// let mut forest = Forest::new();
+/// forest.attach_tree(Tree::new(0));
+/// forest.attach_tree(Tree::new(1));
+/// forest.tree_at_mut(0).unwrap().create_subtree(2);
+///
+/// assert_eq!(forest.trees(), 2);
+/// assert_eq!(forest.iter().collect::<Vec<_>>(), vec![&0, &2, &1]);
+///
+/// let tree = forest.clone().into_tree(-1);
+/// assert_eq!(tree.children(), 2);
+/// assert_eq!(tree.into_forest(), (-1, forest));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(transparent))]
+pub struct Forest<T> {
+    trees: Vec<Tree<T>>
+}
+
+impl<T> Forest<T> {
+    /// Constructs and returns a new, empty `Forest`.
+    pub fn new() -> Self {
+        Self { trees: Vec::new() }
+    }
+
+    /// Returns how many trees this forest contains.
+    pub fn trees(&self) -> usize { self.trees.len() }
+    /// Returns whether this forest contains no trees.
+    pub fn is_empty(&self) -> bool { self.trees.is_empty() }
+
+    pub fn tree_at(&self, i: usize) -> Option<&Tree<T>> { self.trees.get(i) }
+    pub fn tree_at_mut(&mut self, i: usize) -> Option<&mut Tree<T>> { self.trees.get_mut(i) }
+
+    /// Returns the subtree reached by `path`, whose first index picks the tree.
+    /// Returns none if the path is empty or doesn't point to an existing point on the forest.
+    pub fn get(&self, path: &[usize]) -> Option<&Tree<T>> {
+        let (&i, rest) = path.split_first()?;
+        self.tree_at(i)?.get(rest)
+    }
+
+    /// Returns a mutable reference to the subtree reached by `path`, whose first index picks the tree.
+    /// Returns none if the path is empty or doesn't point to an existing point on the forest.
+    pub fn get_mut(&mut self, path: &[usize]) -> Option<&mut Tree<T>> {
+        let (&i, rest) = path.split_first()?;
+        self.tree_at_mut(i)?.get_mut(rest)
+    }
+
+    /// Appends `tree` as the last tree.
+    pub fn attach_tree(&mut self, tree: Tree<T>) {
+        self.trees.push(tree);
+    }
+
+    /// Inserts `tree` at index `i`.
+    /// An index past the last tree is clamped, appending the tree.
+    pub fn insert_tree_at(&mut self, i: usize, tree: Tree<T>) {
+        let i = i.min(self.trees.len());
+        self.trees.insert(i, tree);
+    }
+
+    /// Removes and returns the tree at index `i`.
+    /// Returns none if there is no such tree.
+    pub fn remove_tree(&mut self, i: usize) -> Option<Tree<T>> {
+        if i < self.trees.len() {
+            Some(self.trees.remove(i))
+        } else {
+            None
+        }
+    }
+
+    /// Returns an iterator over the trees.
+    pub fn trees_iter(&self) -> slice::Iter<'_, Tree<T>> { self.trees.iter() }
+    /// Returns an iterator over mutable references to the trees.
+    pub fn trees_iter_mut(&mut self) -> slice::IterMut<'_, Tree<T>> { self.trees.iter_mut() }
+
+    /// Returns a depth-first pre-order iterator over all labels, one tree after another.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { trees: self.trees.iter(), current: None }
+    }
+
+    /// Consumes the forest and returns a tree whose children are its trees, rooted at `label`.
+    pub fn into_tree(self, label: T) -> Tree<T> {
+        let mut tree = Tree::new(label);
+        for child in self.trees {
+            tree.attach_child(child);
+        }
+        tree
+    }
+}
+
+impl<T> Tree<T> {
+    /// Consumes the tree and returns its root label along with a forest of its children.
+    pub fn into_forest(self) -> (T, Forest<T>) {
+        let (label, children) = self.into_parts();
+        (label, Forest { trees: children })
+    }
+}
+
+impl<T> From<Vec<Tree<T>>> for Forest<T> {
+    fn from(trees: Vec<Tree<T>>) -> Self { Self { trees } }
+}
+
+impl<T> From<Tree<T>> for Forest<T> {
+    fn from(tree: Tree<T>) -> Self { Self { trees: vec![tree] } }
+}
+
+impl<T> From<Forest<T>> for Vec<Tree<T>> {
+    fn from(forest: Forest<T>) -> Self { forest.trees }
+}
+
+impl<T> core::iter::FromIterator<Tree<T>> for Forest<T> {
+    fn from_iter<I: IntoIterator<Item = Tree<T>>>(iter: I) -> Self {
+        Self { trees: iter.into_iter().collect() }
+    }
+}
+
+impl<T> IntoIterator for Forest<T> {
+    type Item = Tree<T>;
+    type IntoIter = alloc::vec::IntoIter<Tree<T>>;
+
+    fn into_iter(self) -> Self::IntoIter { self.trees.into_iter() }
+}
+
+impl<'a, T> IntoIterator for &'a Forest<T> {
+    type Item = &'a Tree<T>;
+    type IntoIter = slice::Iter<'a, Tree<T>>;
+
+    fn into_iter(self) -> Self::IntoIter { self.trees.iter() }
+}
+
+/// A depth-first pre-order iterator over the labels of a `Forest`.
+pub struct Iter<'a, T> {
+    trees: slice::Iter<'a, Tree<T>>,
+    current: Option<iter::Iter<'a, T>>
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(label) = self.current.as_mut().and_then(Iterator::next) {
+                return Some(label);
+            }
+            self.current = Some(self.trees.next()?.iter());
+        }
+    }
+}
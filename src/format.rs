@@ -0,0 +1,81 @@
+//! Textual representations of trees.
+
+use alloc::string::String;
+use core::error::Error;
+use core::fmt;
+use core::iter::Peekable;
+use core::str::Chars;
+
+pub mod sexpr;
+pub mod newick;
+pub mod outline;
+pub mod markdown;
+#[cfg(feature = "opml")]
+pub mod opml;
+
+/// An error encountered while parsing a textual representation of a tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    line: usize,
+    column: usize,
+    message: String
+}
+
+impl ParseError {
+    pub(crate) fn new(line: usize, column: usize, message: impl Into<String>) -> Self {
+        Self { line, column, message: message.into() }
+    }
+
+    /// Returns the line, starting from 1, at which the error occurred.
+    pub fn line(&self) -> usize { self.line }
+    /// Returns the column, starting from 1, at which the error occurred.
+    pub fn column(&self) -> usize { self.column }
+    pub fn message(&self) -> &str { &self.message }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl Error for ParseError {}
+
+/// A character cursor keeping track of lines and columns.
+pub(crate) struct Cursor<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+    column: usize
+}
+
+impl<'a> Cursor<'a> {
+    pub(crate) fn new(s: &'a str) -> Self {
+        Self { chars: s.chars().peekable(), line: 1, column: 1 }
+    }
+
+    pub(crate) fn peek(&mut self) -> Option<char> { self.chars.peek().copied() }
+
+    pub(crate) fn bump(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    /// Skips whitespace, returning the position of the next character.
+    pub(crate) fn position(&mut self) -> (usize, usize) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.bump();
+        }
+        (self.line, self.column)
+    }
+
+    /// Returns an error at the current position, without skipping whitespace.
+    pub(crate) fn error(&self, message: impl Into<String>) -> ParseError {
+        ParseError::new(self.line, self.column, message)
+    }
+}
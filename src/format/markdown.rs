@@ -0,0 +1,141 @@
+//! Markdown outlines, either as headings where `#` marks the root and every extra `#`
+//! one level deeper, or as a nested bullet list.
+//!
+//! When parsing, every item becomes a child of the closest preceding item at a
+//! shallower level, and lines that aren't headings or list items respectively are
+//! skipped, as are headings within fenced code blocks.
+
+use alloc::{string::{String, ToString}, vec, vec::Vec, format};
+use core::fmt::Display;
+use core::str::FromStr;
+
+use super::ParseError;
+use crate::tree::Tree;
+
+/// The Markdown constructs a tree is represented with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MarkdownStyle {
+    Headings,
+    List
+}
+
+impl<T: Display> Tree<T> {
+    /// Renders the tree as Markdown in the given `style`.
+    /// Lists are indented by two spaces per level.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    /// use tt::format::markdown::MarkdownStyle;
+    ///
+    /// let mut tree = Tree::new("Title");
+    /// tree.create_subtree("Usage");
+    /// tree.child_at_mut(0).unwrap().create_subtree("Examples");
+    /// tree.create_subtree("License");
+    ///
+    /// assert_eq!(tree.to_markdown(MarkdownStyle::Headings),
+    ///            "# Title\n\n## Usage\n\n### Examples\n\n## License\n");
+    /// assert_eq!(tree.to_markdown(MarkdownStyle::List),
+    ///            "- Title\n  - Usage\n    - Examples\n  - License\n");
+    /// ```
+    pub fn to_markdown(&self, style: MarkdownStyle) -> String {
+        let lines: Vec<String> = self.iter_paths().map(|(path, label)| match style {
+            MarkdownStyle::Headings => format!("{} {}\n", "#".repeat(path.len() + 1), label),
+            MarkdownStyle::List     => format!("{}- {}\n", "  ".repeat(path.len()), label)
+        }).collect();
+
+        match style {
+            MarkdownStyle::Headings => lines.join("\n"),
+            MarkdownStyle::List     => lines.concat()
+        }
+    }
+}
+
+impl<T: FromStr> Tree<T> where T::Err: Display {
+    /// Parses a tree from Markdown in the given `style`, converting every label with
+    /// `FromStr`.
+    /// The first heading or list item is the root, and every following one must be
+    /// at a deeper level.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    /// use tt::format::markdown::MarkdownStyle;
+    ///
+    /// let md = "# Title\nIntro.\n## Usage\n```\n# not a heading\n```\n#### Examples\n## License";
+    /// let tree = Tree::<String>::from_markdown(md, MarkdownStyle::Headings).unwrap();
+    /// assert_eq!(tree.iter().collect::<Vec<_>>(), vec!["Title", "Usage", "Examples", "License"]);
+    /// assert_eq!(tree.height(), 2);
+    ///
+    /// let md = "* Title\n    * Usage\n        + Examples\n    * License\n";
+    /// let list = Tree::<String>::from_markdown(md, MarkdownStyle::List).unwrap();
+    /// assert_eq!(list, tree);
+    /// assert_eq!(Tree::from_markdown(&tree.to_markdown(MarkdownStyle::List), MarkdownStyle::List), Ok(tree));
+    ///
+    /// assert!(Tree::<String>::from_markdown("# A\n# B", MarkdownStyle::Headings).is_err());
+    /// ```
+    pub fn from_markdown(s: &str, style: MarkdownStyle) -> Result<Self, ParseError> {
+        let mut items = Vec::new();
+        let mut fenced = false;
+        for (i, line) in s.lines().enumerate() {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+                fenced = !fenced;
+                continue;
+            }
+
+            let item = match style {
+                MarkdownStyle::Headings if !fenced => heading(line),
+                MarkdownStyle::List                => list_item(line),
+                _                                  => None
+            };
+            if let Some((level, column, text)) = item {
+                let label = text.parse::<T>().map_err(|e| ParseError::new(i + 1, column, e.to_string()))?;
+                items.push((level, i + 1, column, label));
+            }
+        }
+
+        let mut items = items.into_iter();
+        let (root_level, _, _, root) = items.next().ok_or_else(|| ParseError::new(1, 1, "expected a root"))?;
+        let mut stack = vec![(root_level, Tree::new(root))];
+        for (level, line, column, label) in items {
+            if level <= root_level {
+                return Err(ParseError::new(line, column, "expected a single root"));
+            }
+            while stack.last().unwrap().0 >= level {
+                let (_, tree) = stack.pop().unwrap();
+                stack.last_mut().unwrap().1.attach_child(tree);
+            }
+            stack.push((level, Tree::new(label)));
+        }
+
+        while stack.len() > 1 {
+            let (_, tree) = stack.pop().unwrap();
+            stack.last_mut().unwrap().1.attach_child(tree);
+        }
+        Ok(stack.pop().unwrap().1)
+    }
+}
+
+/// Returns the level, the column of the text and the text of an ATX heading.
+fn heading(line: &str) -> Option<(usize, usize, &str)> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    let rest = &line[level..];
+    if level == 0 || !(rest.is_empty() || rest.starts_with(char::is_whitespace)) {
+        return None;
+    }
+    let text = rest.trim();
+    Some((level, line.len() - rest.trim_start().len() + 1, text))
+}
+
+/// Returns the indentation, the column of the text and the text of a bullet list item.
+fn list_item(line: &str) -> Option<(usize, usize, &str)> {
+    let trimmed = line.trim_start();
+    let indent: usize = line[..line.len() - trimmed.len()].chars().map(|c| if c == '\t' { 4 } else { 1 }).sum();
+    let mut chars = trimmed.chars();
+    match (chars.next(), chars.next()) {
+        (Some('-'), Some(' ')) | (Some('*'), Some(' ')) | (Some('+'), Some(' ')) => {
+            let text = trimmed[2..].trim();
+            Some((indent, line.len() - trimmed[2..].trim_start().len() + 1, text))
+        }
+        _ => None
+    }
+}
@@ -0,0 +1,193 @@
+//! The Newick format, where children are listed before their parent's label,
+//! e.g. `(A:0.1,B:0.2,(C,D)E)F;`.
+//!
+//! Labels containing whitespace, punctuation or `_` are written within single quotes,
+//! doubling any inner `'`. Unquoted underscores are read as spaces. Comments in
+//! square brackets are skipped.
+
+use alloc::{string::{String, ToString}, vec::Vec, format};
+use core::fmt;
+
+use super::{ParseError, Cursor};
+use crate::tree::Tree;
+
+/// The label of a node in a Newick tree: its name and optional branch length.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct NewickLabel {
+    pub name: String,
+    pub length: Option<f64>
+}
+
+impl From<String> for NewickLabel {
+    fn from(name: String) -> Self {
+        Self { name, length: None }
+    }
+}
+
+impl fmt::Display for NewickLabel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&quote(&self.name))?;
+        match self.length {
+            Some(length) => write!(f, ":{}", length),
+            None         => Ok(())
+        }
+    }
+}
+
+impl Tree<NewickLabel> {
+    /// Renders the tree in the Newick format.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    /// use tt::format::newick::NewickLabel;
+    ///
+    /// let mut tree = Tree::new(NewickLabel::from(String::from("F")));
+    /// tree.create_subtree(NewickLabel { name: String::from("A"), length: Some(0.1) });
+    /// tree.create_subtree(NewickLabel::from(String::from("E")));
+    /// tree.child_at_mut(1).unwrap().create_subtree(NewickLabel::from(String::from("C d")));
+    ///
+    /// assert_eq!(tree.to_newick(), "(A:0.1,('C d')E)F;");
+    /// ```
+    pub fn to_newick(&self) -> String {
+        let mut newick = self.fold(|label, children: Vec<String>| {
+            if children.is_empty() {
+                label.to_string()
+            } else {
+                format!("({}){}", children.join(","), label)
+            }
+        });
+        newick.push(';');
+        newick
+    }
+
+    /// Parses a tree in the Newick format.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    ///
+    /// let tree = Tree::from_newick("(A:0.1,B_b:0.2,(C,'D''s')E)F;").unwrap();
+    /// let names: Vec<_> = tree.iter().map(|label| label.name.as_str()).collect();
+    /// assert_eq!(names, vec!["F", "A", "B b", "E", "C", "D's"]);
+    /// assert_eq!(tree.child_at(1).unwrap().label().length, Some(0.2));
+    /// assert_eq!(Tree::from_newick(&tree.to_newick()), Ok(tree.clone()));
+    ///
+    /// // Branch lengths can be dropped by mapping to names.
+    /// let names = tree.map(|label| label.name);
+    /// assert_eq!(names.label(), "F");
+    ///
+    /// assert!(Tree::from_newick("(A,B").is_err());
+    /// assert!(Tree::from_newick("(A,B)").is_err());
+    /// assert!(Tree::from_newick("(A:x);").is_err());
+    /// ```
+    pub fn from_newick(s: &str) -> Result<Self, ParseError> {
+        let mut cursor = Cursor::new(s);
+        let mut stack: Vec<Vec<Self>> = Vec::new();
+
+        loop {
+            skip(&mut cursor)?;
+            if cursor.peek() == Some('(') {
+                cursor.bump();
+                stack.push(Vec::new());
+                continue;
+            }
+
+            let mut tree = Tree::new(label(&mut cursor)?);
+            loop {
+                skip(&mut cursor)?;
+                let children = match stack.last_mut() {
+                    Some(children) => children,
+                    None => {
+                        if cursor.bump() != Some(';') {
+                            return Err(cursor.error("expected `;`"));
+                        }
+                        skip(&mut cursor)?;
+                        return match cursor.peek() {
+                            None    => Ok(tree),
+                            Some(_) => Err(cursor.error("expected end of input"))
+                        };
+                    }
+                };
+
+                children.push(tree);
+                match cursor.bump() {
+                    Some(',') => break,
+                    Some(')') => {
+                        let children = stack.pop().unwrap();
+                        tree = Tree::new(label(&mut cursor)?);
+                        for child in children {
+                            tree.attach_child(child);
+                        }
+                    }
+                    _ => return Err(cursor.error("expected `,` or `)`"))
+                }
+            }
+        }
+    }
+}
+
+/// Returns `name`, quoted and escaped if it wouldn't otherwise read back unchanged.
+fn quote(name: &str) -> String {
+    if !name.contains(|c: char| c.is_whitespace() || special(c) || c == '_') {
+        return name.to_string();
+    }
+
+    format!("'{}'", name.replace('\'', "''"))
+}
+
+fn special(c: char) -> bool { "()[]':;,".contains(c) }
+
+/// Skips whitespace and comments.
+fn skip(cursor: &mut Cursor) -> Result<(), ParseError> {
+    cursor.position();
+    while cursor.peek() == Some('[') {
+        let error = cursor.error("unterminated comment");
+        while cursor.bump().ok_or_else(|| error.clone())? != ']' {}
+        cursor.position();
+    }
+    Ok(())
+}
+
+/// Parses an optional name followed by an optional branch length.
+fn label(cursor: &mut Cursor) -> Result<NewickLabel, ParseError> {
+    skip(cursor)?;
+    let mut name = String::new();
+    if cursor.peek() == Some('\'') {
+        let error = cursor.error("unterminated quoted label");
+        cursor.bump();
+        loop {
+            match cursor.bump() {
+                Some('\'') if cursor.peek() == Some('\'') => { cursor.bump(); name.push('\''); }
+                Some('\'') => break,
+                Some(c)    => name.push(c),
+                None       => return Err(error)
+            }
+        }
+    } else {
+        while let Some(c) = cursor.peek() {
+            if c.is_whitespace() || special(c) {
+                break;
+            }
+            name.push(if c == '_' { ' ' } else { c });
+            cursor.bump();
+        }
+    }
+
+    skip(cursor)?;
+    let mut length = None;
+    if cursor.peek() == Some(':') {
+        cursor.bump();
+        skip(cursor)?;
+        let error = cursor.error("expected a branch length");
+        let mut number = String::new();
+        while let Some(c) = cursor.peek() {
+            if c.is_whitespace() || special(c) {
+                break;
+            }
+            number.push(c);
+            cursor.bump();
+        }
+        length = Some(number.parse().map_err(|_| error)?);
+    }
+
+    Ok(NewickLabel { name, length })
+}
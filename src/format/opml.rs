@@ -0,0 +1,267 @@
+//! OPML, the XML outline interchange format.
+//!
+//! The root label is the document title, and every `outline` element within the body
+//! becomes a subtree labelled with its `text` attribute. Other elements and attributes
+//! are ignored.
+
+use alloc::{string::{String, ToString}, vec, vec::Vec, format};
+use core::fmt::Display;
+
+use super::{ParseError, Cursor};
+use crate::tree::Tree;
+
+impl<T: Display> Tree<T> {
+    /// Renders the tree as an OPML 2.0 document.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    ///
+    /// let mut tree = Tree::new("Feeds");
+    /// tree.create_subtree("News & Weather");
+    /// tree.child_at_mut(0).unwrap().create_subtree("\"Local\"");
+    ///
+    /// assert_eq!(tree.to_opml(), "\
+    /// <?xml version=\"1.0\" encoding=\"UTF-8\"?>
+    /// <opml version=\"2.0\">
+    ///   <head>
+    ///     <title>Feeds</title>
+    ///   </head>
+    ///   <body>
+    ///     <outline text=\"News &amp; Weather\">
+    ///       <outline text=\"&quot;Local&quot;\"/>
+    ///     </outline>
+    ///   </body>
+    /// </opml>
+    /// ");
+    /// ```
+    pub fn to_opml(&self) -> String {
+        let mut opml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<opml version=\"2.0\">\n");
+        opml.push_str(&format!("  <head>\n    <title>{}</title>\n  </head>\n  <body>\n", escape(&self.label().to_string())));
+        for child in self.children_iter() {
+            child.write_outline(&mut opml, 2);
+        }
+        opml.push_str("  </body>\n</opml>\n");
+        opml
+    }
+
+    fn write_outline(&self, opml: &mut String, depth: usize) {
+        let indent = "  ".repeat(depth);
+        let text = escape(&self.label().to_string());
+        if self.is_leaf() {
+            opml.push_str(&format!("{}<outline text=\"{}\"/>\n", indent, text));
+        } else {
+            opml.push_str(&format!("{}<outline text=\"{}\">\n", indent, text));
+            for child in self.children_iter() {
+                child.write_outline(opml, depth + 1);
+            }
+            opml.push_str(&format!("{}</outline>\n", indent));
+        }
+    }
+}
+
+impl Tree<String> {
+    /// Parses a tree from an OPML document.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    ///
+    /// let opml = r#"<?xml version="1.0"?>
+    /// <!-- exported -->
+    /// <opml version="1.0">
+    ///   <head><title>Feeds</title><dateCreated>today</dateCreated></head>
+    ///   <body>
+    ///     <outline text="News" title="News">
+    ///       <outline type="rss" text='Local &#38; &lt;World&gt;' xmlUrl="http://example.com"/>
+    ///     </outline>
+    ///     <outline text="Empty"></outline>
+    ///   </body>
+    /// </opml>"#;
+    ///
+    /// let tree = Tree::from_opml(opml).unwrap();
+    /// assert_eq!(tree.iter().collect::<Vec<_>>(), vec!["Feeds", "News", "Local & <World>", "Empty"]);
+    /// assert_eq!(Tree::from_opml(&tree.to_opml()), Ok(tree));
+    ///
+    /// assert!(Tree::from_opml("<opml><body><outline text=\"a\"></body></opml>").is_err());
+    /// ```
+    pub fn from_opml(s: &str) -> Result<Self, ParseError> {
+        let mut cursor = Cursor::new(s);
+        let mut elements: Vec<String> = Vec::new();
+        let mut title = String::new();
+        let mut stack = vec![Tree::new(String::new())];
+
+        loop {
+            let (line, column) = (cursor.line, cursor.column);
+            let error = |message: &str| ParseError::new(line, column, message);
+            match cursor.peek() {
+                None => break,
+                Some('<') => {
+                    cursor.bump();
+                    match cursor.peek() {
+                        Some('?') => { skip_past(&mut cursor, "?>")?; }
+                        Some('!') => {
+                            cursor.bump();
+                            if cursor.peek() == Some('-') { skip_past(&mut cursor, "-->")?; }
+                            else { skip_past(&mut cursor, ">")?; }
+                        }
+                        Some('/') => {
+                            cursor.bump();
+                            let name = name(&mut cursor);
+                            cursor.position();
+                            if cursor.bump() != Some('>') {
+                                return Err(cursor.error("expected `>`"));
+                            }
+                            if elements.pop().as_ref() != Some(&name) {
+                                return Err(error("mismatched closing tag"));
+                            }
+                            if name == "outline" && in_body(&elements) {
+                                let tree = stack.pop().unwrap();
+                                stack.last_mut().unwrap().attach_child(tree);
+                            }
+                        }
+                        _ => {
+                            let name = name(&mut cursor);
+                            if name.is_empty() {
+                                return Err(cursor.error("expected an element name"));
+                            }
+                            let (attributes, closed) = attributes(&mut cursor)?;
+                            if name == "outline" && in_body(&elements) {
+                                let text = attributes.into_iter().find(|(k, _)| k == "text").map(|(_, v)| v);
+                                let tree = Tree::new(text.unwrap_or_default());
+                                if closed {
+                                    stack.last_mut().unwrap().attach_child(tree);
+                                } else {
+                                    stack.push(tree);
+                                }
+                            }
+                            if !closed {
+                                elements.push(name);
+                            }
+                        }
+                    }
+                }
+                Some(_) => {
+                    let mut text = String::new();
+                    while let Some(c) = cursor.peek() {
+                        if c == '<' {
+                            break;
+                        }
+                        text.push(c);
+                        cursor.bump();
+                    }
+                    if elements.last().map(String::as_str) == Some("title") && elements.len() >= 2
+                        && elements[elements.len() - 2] == "head" {
+                        title.push_str(&unescape(&text).map_err(error)?);
+                    }
+                }
+            }
+        }
+
+        if !elements.is_empty() {
+            return Err(cursor.error(format!("unclosed element `{}`", elements.last().unwrap())));
+        }
+        let mut root = stack.pop().unwrap();
+        root.set_label(title.trim().to_string());
+        Ok(root)
+    }
+}
+
+/// Returns whether the innermost elements, besides outlines, are `opml` and `body`.
+fn in_body(elements: &[String]) -> bool {
+    let outer: Vec<&str> = elements.iter().map(String::as_str).filter(|e| *e != "outline").collect();
+    outer == ["opml", "body"]
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+fn unescape(text: &str) -> Result<String, &'static str> {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(i) = rest.find('&') {
+        unescaped.push_str(&rest[..i]);
+        let end = rest[i..].find(';').ok_or("unterminated entity")? + i;
+        let entity = &rest[i + 1..end];
+        let c = match entity {
+            "amp"  => '&',
+            "lt"   => '<',
+            "gt"   => '>',
+            "quot" => '"',
+            "apos" => '\'',
+            _ => {
+                let code = match entity.strip_prefix("#x").or_else(|| entity.strip_prefix("#X")) {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                    None      => entity.strip_prefix('#').and_then(|dec| dec.parse().ok())
+                };
+                code.and_then(core::char::from_u32).ok_or("unknown entity")?
+            }
+        };
+        unescaped.push(c);
+        rest = &rest[end + 1..];
+    }
+    unescaped.push_str(rest);
+    Ok(unescaped)
+}
+
+fn skip_past(cursor: &mut Cursor, end: &str) -> Result<(), ParseError> {
+    let error = cursor.error("unterminated markup");
+    let mut seen = String::new();
+    while !seen.ends_with(end) {
+        seen.push(cursor.bump().ok_or_else(|| error.clone())?);
+    }
+    Ok(())
+}
+
+fn name(cursor: &mut Cursor) -> String {
+    let mut name = String::new();
+    while let Some(c) = cursor.peek() {
+        if c.is_whitespace() || "/>=".contains(c) {
+            break;
+        }
+        name.push(c);
+        cursor.bump();
+    }
+    name
+}
+
+/// Parses the attributes of a start tag up to its `>`, returning them and whether
+/// the tag was self-closing.
+fn attributes(cursor: &mut Cursor) -> Result<(Vec<(String, String)>, bool), ParseError> {
+    let mut attributes = Vec::new();
+    loop {
+        cursor.position();
+        match cursor.peek() {
+            Some('>') => { cursor.bump(); return Ok((attributes, false)); }
+            Some('/') => {
+                cursor.bump();
+                if cursor.bump() != Some('>') {
+                    return Err(cursor.error("expected `>`"));
+                }
+                return Ok((attributes, true));
+            }
+            Some(_) => {
+                let key = name(cursor);
+                cursor.position();
+                if key.is_empty() || cursor.bump() != Some('=') {
+                    return Err(cursor.error("expected an attribute"));
+                }
+                cursor.position();
+                let error = cursor.error("expected a quoted attribute value");
+                let quote = match cursor.bump() {
+                    Some(q) if q == '"' || q == '\'' => q,
+                    _ => return Err(error)
+                };
+                let mut value = String::new();
+                loop {
+                    match cursor.bump() {
+                        Some(c) if c == quote => break,
+                        Some(c) => value.push(c),
+                        None    => return Err(error)
+                    }
+                }
+                attributes.push((key, unescape(&value).map_err(|m| cursor.error(m))?));
+            }
+            None => return Err(cursor.error("unexpected end of input"))
+        }
+    }
+}
@@ -0,0 +1,112 @@
+//! Indented outlines, with one label per line and the depth of each label given by
+//! how many indentation units precede it, e.g.
+//!
+//! ```text
+//! root
+//!     a
+//!         b
+//!     c
+//! ```
+//!
+//! Blank lines are skipped. Labels can't contain newlines.
+
+use alloc::{string::{String, ToString}, vec::Vec};
+use core::fmt::Display;
+use core::str::FromStr;
+
+use super::ParseError;
+use crate::tree::Tree;
+
+impl<T: Display> Tree<T> {
+    /// Renders the tree as an outline, indenting each level by `indent`.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    ///
+    /// let mut tree = Tree::new("root");
+    /// tree.create_subtree("a");
+    /// tree.child_at_mut(0).unwrap().create_subtree("b");
+    /// tree.create_subtree("c");
+    ///
+    /// assert_eq!(tree.to_outline("  "), "root\n  a\n    b\n  c\n");
+    /// ```
+    pub fn to_outline(&self, indent: &str) -> String {
+        let mut outline = String::new();
+        for (path, label) in self.iter_paths() {
+            outline.push_str(&indent.repeat(path.len()));
+            outline.push_str(&label.to_string());
+            outline.push('\n');
+        }
+        outline
+    }
+}
+
+impl<T: FromStr> Tree<T> where T::Err: Display {
+    /// Parses a tree from an outline indented by `indent`, converting every label
+    /// with `FromStr`.
+    /// The first line is the root, and must be the only line without indentation.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    ///
+    /// let tree = Tree::<String>::from_outline("root\n\ta\n\t\tb\n\n\tc\n", "\t").unwrap();
+    /// assert_eq!(tree.iter().collect::<Vec<_>>(), vec!["root", "a", "b", "c"]);
+    /// assert_eq!(Tree::from_outline(&tree.to_outline("\t"), "\t"), Ok(tree));
+    ///
+    /// // Skipping a level.
+    /// let err = Tree::<String>::from_outline("root\n    a\n            b", "    ").unwrap_err();
+    /// assert_eq!(err.line(), 3);
+    /// // Partial indentation.
+    /// assert!(Tree::<String>::from_outline("root\n  a\n   b", "  ").is_err());
+    /// // Several roots.
+    /// assert!(Tree::<String>::from_outline("a\nb", "  ").is_err());
+    /// ```
+    pub fn from_outline(s: &str, indent: &str) -> Result<Self, ParseError> {
+        let mut stack: Vec<Tree<T>> = Vec::new();
+
+        for (i, line) in s.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let (depth, text) = split_indent(line, indent);
+            let column = line.len() - text.len() + 1;
+            if text.starts_with(char::is_whitespace) {
+                return Err(ParseError::new(i + 1, column, "inconsistent indentation"));
+            }
+            if (depth == 0) != stack.is_empty() {
+                return Err(ParseError::new(i + 1, column, "expected a single root"));
+            }
+            if depth > stack.len() {
+                return Err(ParseError::new(i + 1, column, "indented more than one level deeper than its parent"));
+            }
+
+            let label = text.parse::<T>().map_err(|e| ParseError::new(i + 1, column, e.to_string()))?;
+            collapse(&mut stack, depth);
+            stack.push(Tree::new(label));
+        }
+
+        collapse(&mut stack, 1);
+        stack.pop().ok_or_else(|| ParseError::new(1, 1, "expected a root"))
+    }
+}
+
+/// Returns how many times `line` starts with `indent`, and what remains.
+fn split_indent<'a>(mut line: &'a str, indent: &str) -> (usize, &'a str) {
+    let mut depth = 0;
+    if indent.is_empty() {
+        return (depth, line);
+    }
+    while let Some(rest) = line.strip_prefix(indent) {
+        line = rest;
+        depth += 1;
+    }
+    (depth, line)
+}
+
+/// Attaches trees on `stack` to their parents until only `depth` trees remain.
+fn collapse<T>(stack: &mut Vec<Tree<T>>, depth: usize) {
+    while stack.len() > depth.max(1) {
+        let tree = stack.pop().unwrap();
+        stack.last_mut().unwrap().attach_child(tree);
+    }
+}
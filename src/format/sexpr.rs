@@ -0,0 +1,169 @@
+//! S-expressions, where every tree is a parenthesised label followed by its children,
+//! e.g. `(root (a (b)) (c))`.
+//!
+//! Labels containing whitespace, parentheses, quotes or backslashes, as well as empty
+//! labels, are written within double quotes, escaping `"`, `\` and newlines with `\`.
+
+use alloc::{string::{String, ToString}, vec::Vec, format};
+use core::fmt::Display;
+use core::str::FromStr;
+
+use super::{ParseError, Cursor};
+use crate::tree::Tree;
+
+impl<T: Display> Tree<T> {
+    /// Renders the tree as an S-expression.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    ///
+    /// let mut tree = Tree::new(String::from("root"));
+    /// tree.create_subtree(String::from("a"));
+    /// tree.child_at_mut(0).unwrap().create_subtree(String::from("b"));
+    /// tree.create_subtree(String::from("c d"));
+    ///
+    /// assert_eq!(tree.to_sexpr(), "(root (a (b)) (\"c d\"))");
+    /// ```
+    pub fn to_sexpr(&self) -> String {
+        self.fold(|label, children: Vec<String>| {
+            let mut sexpr = format!("({}", quote(&label.to_string()));
+            for child in children {
+                sexpr.push(' ');
+                sexpr.push_str(&child);
+            }
+            sexpr.push(')');
+            sexpr
+        })
+    }
+}
+
+impl<T: FromStr> Tree<T> where T::Err: Display {
+    /// Parses a tree from an S-expression, converting every label with `FromStr`.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    ///
+    /// let tree = Tree::<String>::from_sexpr("(root (a (b)) (\"c d\"))").unwrap();
+    /// assert_eq!(tree.iter().collect::<Vec<_>>(), vec!["root", "a", "b", "c d"]);
+    /// assert_eq!(Tree::from_sexpr(&tree.to_sexpr()), Ok(tree));
+    ///
+    /// let tree = Tree::<i32>::from_sexpr("(1\n  (2)\n  (3))").unwrap();
+    /// assert_eq!(tree.iter().sum::<i32>(), 6);
+    ///
+    /// let err = Tree::<i32>::from_sexpr("(1\n  (x))").unwrap_err();
+    /// assert_eq!((err.line(), err.column()), (2, 4));
+    /// assert!(Tree::<String>::from_sexpr("(a (b)").is_err());
+    /// assert!(Tree::<String>::from_sexpr("(a) (b)").is_err());
+    /// ```
+    pub fn from_sexpr(s: &str) -> Result<Self, ParseError> {
+        let mut lexer = Lexer::new(s);
+        let mut stack: Vec<Tree<T>> = Vec::new();
+
+        loop {
+            let (line, column) = lexer.position();
+            match lexer.next()? {
+                Some(Token::Open) => {
+                    let (line, column) = lexer.position();
+                    let label = match lexer.next()? {
+                        Some(Token::Atom(atom)) => atom.parse::<T>()
+                            .map_err(|e| ParseError::new(line, column, e.to_string()))?,
+                        _ => return Err(ParseError::new(line, column, "expected a label"))
+                    };
+                    stack.push(Tree::new(label));
+                }
+                Some(Token::Close) => {
+                    let tree = stack.pop().ok_or_else(|| ParseError::new(line, column, "unexpected `)`"))?;
+                    match stack.last_mut() {
+                        Some(parent) => parent.attach_child(tree),
+                        None => {
+                            let (line, column) = lexer.position();
+                            return match lexer.next()? {
+                                None    => Ok(tree),
+                                Some(_) => Err(ParseError::new(line, column, "expected end of input"))
+                            };
+                        }
+                    }
+                }
+                Some(Token::Atom(_)) => return Err(ParseError::new(line, column, "expected `(` or `)`")),
+                None => return Err(ParseError::new(line, column, "unexpected end of input"))
+            }
+        }
+    }
+}
+
+/// Returns `label`, quoted and escaped if it wouldn't otherwise read back as a single atom.
+fn quote(label: &str) -> String {
+    let special = |c: char| c.is_whitespace() || "()\"\\".contains(c);
+    if !label.is_empty() && !label.contains(special) {
+        return label.to_string();
+    }
+
+    let mut quoted = String::from("\"");
+    for c in label.chars() {
+        match c {
+            '"' | '\\' => { quoted.push('\\'); quoted.push(c); }
+            '\n'       => quoted.push_str("\\n"),
+            _          => quoted.push(c)
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+enum Token {
+    Open,
+    Close,
+    Atom(String)
+}
+
+struct Lexer<'a> {
+    cursor: Cursor<'a>
+}
+
+impl<'a> Lexer<'a> {
+    fn new(s: &'a str) -> Self {
+        Self { cursor: Cursor::new(s) }
+    }
+
+    /// Returns the position of the next token.
+    fn position(&mut self) -> (usize, usize) { self.cursor.position() }
+
+    fn next(&mut self) -> Result<Option<Token>, ParseError> {
+        let (line, column) = self.position();
+        let token = match self.cursor.peek() {
+            None      => return Ok(None),
+            Some('(') => { self.cursor.bump(); Token::Open }
+            Some(')') => { self.cursor.bump(); Token::Close }
+            Some('"') => {
+                self.cursor.bump();
+                let mut atom = String::new();
+                loop {
+                    match self.cursor.bump() {
+                        Some('"')  => break,
+                        Some('\\') => match self.cursor.bump() {
+                            Some('n') => atom.push('\n'),
+                            Some(c)   => atom.push(c),
+                            None      => return Err(ParseError::new(line, column, "unterminated string"))
+                        },
+                        Some(c)    => atom.push(c),
+                        None       => return Err(ParseError::new(line, column, "unterminated string"))
+                    }
+                }
+                Token::Atom(atom)
+            }
+            Some(_)   => {
+                let mut atom = String::new();
+                while let Some(c) = self.cursor.peek() {
+                    if c.is_whitespace() || "()\"".contains(c) {
+                        break;
+                    }
+                    atom.push(c);
+                    self.cursor.bump();
+                }
+                Token::Atom(atom)
+            }
+        };
+
+        Ok(Some(token))
+    }
+}
@@ -0,0 +1,82 @@
+//! Structural hashes of subtrees, Merkle style: the hash of a subtree covers its label and,
+//! in order, the hashes of its children, so equal subtrees hash alike wherever they appear
+//! and a change anywhere within a subtree changes its hash.
+//!
+//! Hashes are computed with 64-bit FNV-1a, so they are the same on every run, unlike those of `std`'s
+//! randomly seeded hashers. They are portable too, matching between targets such as `wasm32` and
+//! `x86_64` as long as the labels hash alike on both, which labels holding `usize` or `isize` don't.
+
+use alloc::vec::Vec;
+use core::hash::{Hash, Hasher};
+
+use super::tree::Tree;
+
+/// The 64-bit FNV-1a hasher.
+struct Fnv(u64);
+
+impl Default for Fnv {
+    fn default() -> Self { Fnv(0xcbf2_9ce4_8422_2325) }
+}
+
+impl Hasher for Fnv {
+    fn finish(&self) -> u64 { self.0 }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+impl<T: Hash> Tree<T> {
+    /// Returns a tree of the same shape pairing each label with the structural hash of its subtree.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    ///
+    /// let mut tree = Tree::new("root");
+    /// tree.create_subtree("a");
+    /// tree.child_at_mut(0).unwrap().create_subtree("b");
+    /// tree.create_subtree("a");
+    /// let mut other = tree.clone();
+    /// other.child_at_mut(1).unwrap().create_subtree("b");
+    ///
+    /// let hashes = tree.hash_subtrees();
+    /// let hash = |path: &[usize]| hashes.get(path).unwrap().label().1;
+    /// assert_ne!(hash(&[0]), hash(&[1]));
+    /// assert_eq!(hash(&[]), tree.structural_hash());
+    ///
+    /// let others = other.hash_subtrees();
+    /// let other_hash = |path: &[usize]| others.get(path).unwrap().label().1;
+    /// assert_eq!(other_hash(&[0]), other_hash(&[1]));
+    /// assert_eq!(other_hash(&[0]), hash(&[0]));
+    /// assert_ne!(other_hash(&[]), hash(&[]));
+    /// ```
+    pub fn hash_subtrees(&self) -> Tree<(&T, u64)> {
+        let children: Vec<_> = self.children_iter().map(Tree::hash_subtrees).collect();
+        let hash = combine(self.label(), children.iter().map(|child| child.label().1));
+
+        let mut hashed = Tree::new((self.label(), hash));
+        for child in children {
+            hashed.attach_child(child);
+        }
+        hashed
+    }
+
+    /// Returns the structural hash of the tree.
+    pub fn structural_hash(&self) -> u64 {
+        self.fold(|label, children: Vec<u64>| combine(label, children.into_iter()))
+    }
+}
+
+/// Returns the hash of a subtree with `label` whose children hash to `children`.
+pub(crate) fn combine<T: Hash, I: ExactSizeIterator<Item = u64>>(label: &T, children: I) -> u64 {
+    let mut hasher = Fnv::default();
+    label.hash(&mut hasher);
+    hasher.write_u64(children.len() as u64);
+    for child in children {
+        hasher.write_u64(child);
+    }
+    hasher.finish()
+}
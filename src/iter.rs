@@ -0,0 +1,239 @@
+use alloc::{vec, vec::Vec};
+use alloc::collections::VecDeque;
+
+use super::tree::{Tree, Path};
+
+/// A depth-first pre-order iterator over the labels of a tree.
+/// Created by `Tree::iter`.
+pub struct Iter<'a, T> {
+    stack: Vec<&'a Tree<T>>
+}
+
+impl<'a, T> Iter<'a, T> {
+    pub(crate) fn new(tree: &'a Tree<T>) -> Self {
+        Self { stack: vec![tree] }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let tree = self.stack.pop()?;
+        self.stack.extend(tree.children_iter().rev());
+        Some(tree.label())
+    }
+}
+
+/// A depth-first pre-order iterator over mutable references to the labels of a tree.
+/// Created by `Tree::iter_mut`.
+pub struct IterMut<'a, T> {
+    stack: Vec<&'a mut Tree<T>>
+}
+
+impl<'a, T> IterMut<'a, T> {
+    pub(crate) fn new(tree: &'a mut Tree<T>) -> Self {
+        Self { stack: vec![tree] }
+    }
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (label, children) = self.stack.pop()?.split_mut();
+        self.stack.extend(children.iter_mut().rev());
+        Some(label)
+    }
+}
+
+/// A breadth-first iterator over the labels of a tree.
+/// Created by `Tree::iter_bfs`.
+pub struct Bfs<'a, T> {
+    queue: VecDeque<&'a Tree<T>>
+}
+
+impl<'a, T> Bfs<'a, T> {
+    pub(crate) fn new(tree: &'a Tree<T>) -> Self {
+        Self { queue: VecDeque::from(vec![tree]) }
+    }
+}
+
+impl<'a, T> Iterator for Bfs<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let tree = self.queue.pop_front()?;
+        self.queue.extend(tree.children_iter());
+        Some(tree.label())
+    }
+}
+
+/// A depth-first post-order iterator over the labels of a tree, yielding children
+/// before their parent.
+/// Created by `Tree::iter_post`.
+pub struct PostIter<'a, T> {
+    stack: Vec<(&'a Tree<T>, usize)>
+}
+
+impl<'a, T> PostIter<'a, T> {
+    pub(crate) fn new(tree: &'a Tree<T>) -> Self {
+        Self { stack: vec![(tree, 0)] }
+    }
+}
+
+impl<'a, T> Iterator for PostIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (tree, i) = self.stack.last_mut()?;
+            let tree = *tree;
+            match tree.child_at(*i) {
+                Some(child) => {
+                    *i += 1;
+                    self.stack.push((child, 0));
+                }
+                None => {
+                    self.stack.pop();
+                    return Some(tree.label());
+                }
+            }
+        }
+    }
+}
+
+/// A depth-first pre-order iterator over the labels of a tree, each paired with its path.
+/// Created by `Tree::iter_paths`.
+pub struct PathIter<'a, T> {
+    stack: Vec<(Path, &'a Tree<T>)>
+}
+
+impl<'a, T> PathIter<'a, T> {
+    pub(crate) fn new(tree: &'a Tree<T>) -> Self {
+        Self { stack: vec![(Path::new(), tree)] }
+    }
+}
+
+impl<'a, T> Iterator for PathIter<'a, T> {
+    type Item = (Path, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (path, tree) = self.stack.pop()?;
+        for (i, child) in tree.children_iter().enumerate().rev() {
+            let mut child_path = path.clone();
+            child_path.push(i);
+            self.stack.push((child_path, child));
+        }
+        Some((path, tree.label()))
+    }
+}
+
+/// A depth-first iterator over the labels of the leaves of a tree.
+/// Created by `Tree::leaves`.
+pub struct Leaves<'a, T> {
+    stack: Vec<&'a Tree<T>>
+}
+
+impl<'a, T> Leaves<'a, T> {
+    pub(crate) fn new(tree: &'a Tree<T>) -> Self {
+        Self { stack: vec![tree] }
+    }
+}
+
+impl<'a, T> Iterator for Leaves<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let tree = self.stack.pop()?;
+            if tree.is_leaf() {
+                return Some(tree.label());
+            }
+            self.stack.extend(tree.children_iter().rev());
+        }
+    }
+}
+
+/// A depth-first iterator over the labels of the leaves of a tree, each paired with its path.
+/// Created by `Tree::leaf_paths`.
+pub struct LeafPaths<'a, T> {
+    stack: Vec<(Path, &'a Tree<T>)>
+}
+
+impl<'a, T> LeafPaths<'a, T> {
+    pub(crate) fn new(tree: &'a Tree<T>) -> Self {
+        Self { stack: vec![(Path::new(), tree)] }
+    }
+}
+
+impl<'a, T> Iterator for LeafPaths<'a, T> {
+    type Item = (Path, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (path, tree) = self.stack.pop()?;
+            if tree.is_leaf() {
+                return Some((path, tree.label()));
+            }
+            for (i, child) in tree.children_iter().enumerate().rev() {
+                let mut child_path = path.clone();
+                child_path.push(i);
+                self.stack.push((child_path, child));
+            }
+        }
+    }
+}
+
+/// A depth-first pre-order iterator over the owned labels of a tree.
+/// Created by the `IntoIterator` implementation of `Tree`.
+pub struct IntoIter<T> {
+    stack: Vec<Tree<T>>
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (label, children) = self.stack.pop()?.into_parts();
+        self.stack.extend(children.into_iter().rev());
+        Some(label)
+    }
+}
+
+/// Yields the labels in depth-first pre-order, the same order as `Tree::iter`.
+/// # Example
+/// ```
+/// use std::collections::HashSet;
+/// use tt::tree::Tree;
+///
+/// let mut tree = Tree::new(String::from("a"));
+/// tree.create_subtree(String::from("b"));
+/// tree.child_at_mut(0).unwrap().create_subtree(String::from("c"));
+/// tree.create_subtree(String::from("a"));
+///
+/// let set: HashSet<String> = tree.into_iter().collect();
+/// assert_eq!(set.len(), 3);
+/// ```
+impl<T> IntoIterator for Tree<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { stack: vec![self] }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Tree<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter { self.iter() }
+}
+
+impl<'a, T> IntoIterator for &'a mut Tree<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter { self.iter_mut() }
+}
@@ -0,0 +1,274 @@
+//! Conversion between ad-hoc JSON-like documents and trees of strings.
+//!
+//! Any self-describing serde data, such as a `serde_json::Value`, maps to a tree:
+//! * An object becomes one child per member, labelled with its key.
+//! * An array becomes one child per element, labelled with its index.
+//! * A scalar becomes a single leaf child, labelled with its text.
+//! * A null has no children.
+//!
+//! The root label is empty. Going back, nodes without children become null, nodes
+//! with a single leaf child become scalars, nodes whose children are labelled `0`,
+//! `1`, ... in order become arrays and all other nodes become objects. Empty objects
+//! and arrays therefore come back as null, and scalar strings that read as numbers,
+//! booleans or `null` come back as such.
+
+use alloc::{string::{String, ToString}, vec, vec::Vec};
+use core::fmt;
+
+use serde::de::{self, Deserializer, IntoDeserializer, Visitor, MapAccess, SeqAccess};
+use serde::de::value::{BorrowedStrDeserializer, Error, MapDeserializer, SeqDeserializer};
+use serde::ser::{Serialize, Serializer, SerializeMap, SerializeSeq};
+
+use super::tree::Tree;
+
+impl Tree<String> {
+    /// Constructs and returns a new `Tree` from a self-describing document such as a
+    /// `serde_json::Value`.
+    /// # Example
+    /// ```
+    /// use serde::de::value::{MapDeserializer, Error};
+    /// use tt::tree::Tree;
+    ///
+    /// let document = MapDeserializer::<_, Error>::new(vec![("name", "tt"), ("version", "0.1")].into_iter());
+    /// let tree = Tree::from_json_value(document).unwrap();
+    /// assert_eq!(tree.iter().collect::<Vec<_>>(), vec!["", "name", "tt", "version", "0.1"]);
+    /// ```
+    pub fn from_json_value<'de, D: Deserializer<'de>>(value: D) -> Result<Self, D::Error> {
+        let mut tree = Tree::new(String::new());
+        for child in value.deserialize_any(ContentVisitor)? {
+            tree.attach_child(child);
+        }
+        Ok(tree)
+    }
+
+    /// Returns a view of the tree as a document, serializing as one and deserializing into any type.
+    /// It turns into a `serde_json::Value` by `serde_json::to_value`, this crate not depending on `serde_json`.
+    /// The root label is ignored.
+    /// # Example
+    /// ```
+    /// use serde::Deserialize;
+    /// use tt::tree::Tree;
+    ///
+    /// #[derive(Debug, PartialEq, Deserialize)]
+    /// struct Package<'a> {
+    ///     name: &'a str,
+    ///     tags: (&'a str, bool),
+    ///     size: u8,
+    ///     license: Option<&'a str>
+    /// }
+    ///
+    /// let tree = Tree::<String>::from_sexpr("(package (name (tt)) (tags (0 (tree)) (1 (true))) (size (3)) (license))").unwrap();
+    /// let package = Package::deserialize(tree.to_json_value()).unwrap();
+    /// assert_eq!(package, Package { name: "tt", tags: ("tree", true), size: 3, license: None });
+    ///
+    /// let copy = Tree::from_json_value(tree.to_json_value()).unwrap();
+    /// assert!(copy.children_iter().eq(tree.children_iter()));
+    /// ```
+    pub fn to_json_value(&self) -> JsonValue<'_> { JsonValue(self) }
+}
+
+/// A view of a tree of strings as a JSON-like document.
+/// Created by `Tree::to_json_value`.
+///
+/// Deserializing reads scalars the way they serialize, and nodes without children as null,
+/// or as empty arrays and objects where those are expected. Enums aren't supported.
+#[derive(Debug, Clone, Copy)]
+pub struct JsonValue<'a>(&'a Tree<String>);
+
+/// What a node of a document stands for.
+enum Kind<'a> {
+    Null,
+    Bool(bool),
+    I64(i64),
+    U64(u64),
+    F64(f64),
+    Str(&'a str),
+    Array,
+    Object
+}
+
+impl<'a> JsonValue<'a> {
+    fn kind(&self) -> Kind<'a> {
+        let tree = self.0;
+        match tree.child_at(0) {
+            None => Kind::Null,
+            Some(child) if tree.children() == 1 && child.is_leaf() => scalar(child.label()),
+            Some(_) if tree.children_iter().enumerate().all(|(i, c)| *c.label() == i.to_string()) => Kind::Array,
+            Some(_) => Kind::Object
+        }
+    }
+
+    fn elements(self) -> SeqDeserializer<impl Iterator<Item = JsonValue<'a>>, Error> {
+        SeqDeserializer::new(self.0.children_iter().map(JsonValue))
+    }
+
+    fn members(self) -> MapDeserializer<'a, impl Iterator<Item = (BorrowedStrDeserializer<'a, Error>, JsonValue<'a>)>, Error> {
+        MapDeserializer::new(self.0.children_iter().map(|c| (BorrowedStrDeserializer::new(c.label()), JsonValue(c))))
+    }
+}
+
+fn scalar(label: &str) -> Kind<'_> {
+    if label == "null" {
+        Kind::Null
+    } else if let Ok(b) = label.parse::<bool>() {
+        Kind::Bool(b)
+    } else if let Ok(n) = label.parse::<i64>() {
+        Kind::I64(n)
+    } else if let Ok(n) = label.parse::<u64>() {
+        Kind::U64(n)
+    } else if let Some(n) = label.parse::<f64>().ok().filter(|n| n.is_finite()) {
+        Kind::F64(n)
+    } else {
+        Kind::Str(label)
+    }
+}
+
+impl<'a> Serialize for JsonValue<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.kind() {
+            Kind::Null    => serializer.serialize_unit(),
+            Kind::Bool(b) => serializer.serialize_bool(b),
+            Kind::I64(n)  => serializer.serialize_i64(n),
+            Kind::U64(n)  => serializer.serialize_u64(n),
+            Kind::F64(n)  => serializer.serialize_f64(n),
+            Kind::Str(s)  => serializer.serialize_str(s),
+            Kind::Array   => {
+                let mut seq = serializer.serialize_seq(Some(self.0.children()))?;
+                for child in self.0.children_iter() {
+                    seq.serialize_element(&JsonValue(child))?;
+                }
+                seq.end()
+            }
+            Kind::Object  => {
+                let mut map = serializer.serialize_map(Some(self.0.children()))?;
+                for child in self.0.children_iter() {
+                    map.serialize_entry(child.label(), &JsonValue(child))?;
+                }
+                map.end()
+            }
+        }
+    }
+}
+
+impl<'de> Deserializer<'de> for JsonValue<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.kind() {
+            Kind::Null    => visitor.visit_unit(),
+            Kind::Bool(b) => visitor.visit_bool(b),
+            Kind::I64(n)  => visitor.visit_i64(n),
+            Kind::U64(n)  => visitor.visit_u64(n),
+            Kind::F64(n)  => visitor.visit_f64(n),
+            Kind::Str(s)  => visitor.visit_borrowed_str(s),
+            Kind::Array   => visitor.visit_seq(self.elements()),
+            Kind::Object  => visitor.visit_map(self.members())
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.kind() {
+            Kind::Null => visitor.visit_none(),
+            _          => visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.kind() {
+            Kind::Null => visitor.visit_seq(self.elements()),
+            _          => self.deserialize_any(visitor)
+        }
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, _: usize, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.kind() {
+            Kind::Null => visitor.visit_map(self.members()),
+            _          => self.deserialize_any(visitor)
+        }
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(self, _: &'static str, _: &'static [&'static str], visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _: &'static str, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+        unit unit_struct tuple_struct enum identifier ignored_any
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for JsonValue<'de> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self { self }
+}
+
+/// Visits a value, producing the children of the node holding it.
+struct ContentVisitor;
+
+impl ContentVisitor {
+    fn scalar<E>(self, label: impl ToString) -> Result<Vec<Tree<String>>, E> {
+        Ok(vec![Tree::new(label.to_string())])
+    }
+}
+
+impl<'de> Visitor<'de> for ContentVisitor {
+    type Value = Vec<Tree<String>>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("any value")
+    }
+
+    fn visit_bool<E: de::Error>(self, v: bool) -> Result<Self::Value, E> { self.scalar(v) }
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> { self.scalar(v) }
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> { self.scalar(v) }
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<Self::Value, E> { self.scalar(v) }
+    fn visit_char<E: de::Error>(self, v: char) -> Result<Self::Value, E> { self.scalar(v) }
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> { self.scalar(v) }
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> { Ok(Vec::new()) }
+    fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> { Ok(Vec::new()) }
+
+    fn visit_some<D: Deserializer<'de>>(self, d: D) -> Result<Self::Value, D::Error> {
+        d.deserialize_any(self)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut children = Vec::new();
+        while let Some(Content(content)) = seq.next_element()? {
+            let mut child = Tree::new(children.len().to_string());
+            for grandchild in content {
+                child.attach_child(grandchild);
+            }
+            children.push(child);
+        }
+        Ok(children)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut children = Vec::new();
+        while let Some((key, Content(content))) = map.next_entry::<String, Content>()? {
+            let mut child = Tree::new(key);
+            for grandchild in content {
+                child.attach_child(grandchild);
+            }
+            children.push(child);
+        }
+        Ok(children)
+    }
+}
+
+struct Content(Vec<Tree<String>>);
+
+impl<'de> de::Deserialize<'de> for Content {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        d.deserialize_any(ContentVisitor).map(Content)
+    }
+}
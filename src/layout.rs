@@ -0,0 +1,98 @@
+//! Tidy drawing coordinates for trees, after Reingold and Tilford.
+//!
+//! Every node gets a position on a grid where levels lie one unit apart and neighbouring
+//! nodes on a level at least one unit apart: parents are centered above their first and last
+//! children, subtrees are packed as close as their contours allow, and a subtree is drawn
+//! the same wherever it appears.
+
+use alloc::vec::Vec;
+
+use super::tree::Tree;
+
+/// The position of a node in a layout.
+/// `x` grows to the right, starting at `0.0` for the leftmost node, and `y` is the depth of the node.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64
+}
+
+/// A laid out subtree: the offsets of the roots of subtrees from their parents,
+/// and the extents of each level relative to the root.
+struct Shape {
+    offsets: Tree<f64>,
+    left: Vec<f64>,
+    right: Vec<f64>
+}
+
+impl<T> Tree<T> {
+    /// Lays out the tree as a tidy drawing, returning a tree of the same shape pairing each
+    /// label with the position of its node.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    /// use tt::layout::Point;
+    ///
+    /// let mut tree = Tree::new("root");
+    /// tree.create_subtree("a");
+    /// tree.child_at_mut(0).unwrap().create_subtree("b");
+    /// tree.child_at_mut(0).unwrap().create_subtree("c");
+    /// tree.create_subtree("d");
+    ///
+    /// let layout = tree.layout();
+    /// let points: Vec<_> = layout.iter().map(|(label, point)| (**label, (point.x, point.y))).collect();
+    /// assert_eq!(points, vec![
+    ///     ("root", (1.0, 0.0)),
+    ///     ("a", (0.5, 1.0)),
+    ///     ("b", (0.0, 2.0)),
+    ///     ("c", (1.0, 2.0)),
+    ///     ("d", (1.5, 1.0))
+    /// ]);
+    /// assert_eq!(Tree::new(0).layout().label().1, Point { x: 0.0, y: 0.0 });
+    /// ```
+    pub fn layout(&self) -> Tree<(&T, Point)> {
+        let shape = self.fold(|_, children: Vec<Shape>| pack(children));
+        let x = -shape.left.iter().copied().fold(0.0, f64::min);
+        place(self, &shape.offsets, x, 0)
+    }
+}
+
+/// Packs the shapes of children side by side, as close as their contours allow,
+/// and returns the shape of their parent centered above them.
+fn pack(children: Vec<Shape>) -> Shape {
+    let mut offsets = Tree::new(0.0);
+    let (mut left, mut right): (Vec<f64>, Vec<f64>) = (Vec::new(), Vec::new());
+    let mut xs = Vec::with_capacity(children.len());
+
+    for child in children {
+        let x = right.iter().zip(&child.left).map(|(r, l)| r - l + 1.0).fold(0.0, f64::max);
+        for (depth, (&l, &r)) in child.left.iter().zip(&child.right).enumerate() {
+            if depth < right.len() {
+                right[depth] = x + r;
+            } else {
+                left.push(x + l);
+                right.push(x + r);
+            }
+        }
+        xs.push(x);
+        offsets.attach_child(child.offsets);
+    }
+
+    let mid = xs.last().map_or(0.0, |last| last / 2.0);
+    for (child, x) in offsets.children_iter_mut().zip(xs) {
+        child.set_label(x - mid);
+    }
+    left.iter_mut().chain(right.iter_mut()).for_each(|x| *x -= mid);
+    left.insert(0, 0.0);
+    right.insert(0, 0.0);
+    Shape { offsets, left, right }
+}
+
+/// Pairs each label of `tree` with its position, the root lying at `x` on level `depth`.
+fn place<'a, T>(tree: &'a Tree<T>, offsets: &Tree<f64>, x: f64, depth: usize) -> Tree<(&'a T, Point)> {
+    let mut placed = Tree::new((tree.label(), Point { x, y: depth as f64 }));
+    for (child, offset) in tree.children_iter().zip(offsets.children_iter()) {
+        placed.attach_child(place(child, offset, x + offset.label(), depth + 1));
+    }
+    placed
+}
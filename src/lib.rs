@@ -1,5 +1,46 @@
 //! # **TT** - **T**ree **T**ool
 //! TT is a library for building and viewing trees that are compatible with Serde.
+//!
+//! ## Features
+//! * `std` (default) - Links the standard library. Without it, TT only needs `alloc`.
+//! * `serde` (default) - Serde support for trees and the serde based representations.
+//! * `opml` - OPML import and export.
+//! * `cli` - The `tt` binary, for manipulating trees in shell pipelines.
+//! * `crdt` - A conflict-free replicated tree.
+//! * `view` - A collapsible, keyboard-driven tree view for terminal UIs.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 
 pub mod tree;
 pub mod focus;
+pub mod iter;
+pub mod printer;
+pub mod export;
+pub mod layout;
+pub mod format;
+pub mod arena;
+pub mod flat;
+pub mod persistent;
+pub mod diff;
+pub mod merge;
+pub mod hash;
+pub mod dag;
+#[cfg(feature = "crdt")]
+pub mod crdt;
+pub mod forest;
+pub mod zipper;
+pub mod command;
+pub mod script;
+#[cfg(feature = "view")]
+pub mod view;
+#[cfg(feature = "serde")]
+pub mod json;
+#[cfg(feature = "serde")]
+pub mod compact;
+#[cfg(feature = "serde")]
+pub mod borrowed;
+#[cfg(feature = "serde")]
+pub mod limits;
+#[cfg(feature = "serde")]
+pub mod preorder;
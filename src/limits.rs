@@ -0,0 +1,190 @@
+//! Deserialization of trees from untrusted input, with limits on depth and size.
+//!
+//! The derived `Deserialize` of `Tree` recurses once per level, so deeply nested input
+//! can overflow the stack. `Limits` reads the same representation, but fails with an
+//! error as soon as the input nests too deeply or holds too many nodes.
+//! # Example
+//! ```
+//! use serde::Deserialize;
+//! use tt::tree::Tree;
+//!
+//! #[derive(Deserialize)]
+//! struct Document {
+//!     #[serde(deserialize_with = "tt::limits::deserialize")]
+//!     outline: Tree<String>
+//! }
+//! ```
+
+use alloc::{string::String, vec::Vec, format};
+use core::cell::Cell;
+use core::fmt;
+use core::marker::PhantomData;
+
+use serde::de::{self, Deserializer, DeserializeSeed, Visitor, SeqAccess, MapAccess, IgnoredAny};
+use serde::Deserialize;
+
+use super::tree::Tree;
+
+/// Limits on the trees accepted by deserialization.
+/// # Example
+/// ```
+/// use tt::tree::Tree;
+/// use tt::limits::Limits;
+///
+/// // A chain of `depth` nodes below the root, as a document in the derived representation.
+/// fn chain(key: &str, depth: usize) -> Tree<String> {
+///     let mut node = Tree::new(String::from(key));
+///     node.attach_child(Tree::<String>::from_sexpr("(label (node))").unwrap());
+///     let mut children = Tree::new(String::from("children"));
+///     if depth > 0 {
+///         children.attach_child(chain("0", depth - 1));
+///     }
+///     node.attach_child(children);
+///     node
+/// }
+///
+/// let document = chain("", 3);
+/// let tree: Tree<String> = Limits::new().max_depth(3).deserialize(document.to_json_value()).unwrap();
+/// assert_eq!(tree.height(), 3);
+///
+/// assert!(Limits::new().max_depth(2).deserialize::<String, _>(document.to_json_value()).is_err());
+/// assert!(Limits::new().max_nodes(3).deserialize::<String, _>(document.to_json_value()).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Limits {
+    max_depth: usize,
+    max_nodes: usize
+}
+
+impl Limits {
+    /// Constructs and returns new `Limits`, allowing a depth of 128 and any amount of nodes.
+    pub fn new() -> Self {
+        Self { max_depth: 128, max_nodes: usize::MAX }
+    }
+
+    /// Sets the maximum depth, a lone root having depth `0`.
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Sets the maximum amount of nodes, including the root.
+    pub fn max_nodes(mut self, max_nodes: usize) -> Self {
+        self.max_nodes = max_nodes;
+        self
+    }
+
+    /// Deserializes a tree, in the derived representation, within these limits.
+    pub fn deserialize<'de, T: Deserialize<'de>, D: Deserializer<'de>>(&self, deserializer: D) -> Result<Tree<T>, D::Error> {
+        let nodes = Cell::new(0);
+        TreeSeed { limits: self, depth: 0, nodes: &nodes, label: PhantomData }.deserialize(deserializer)
+    }
+}
+
+impl Default for Limits {
+    fn default() -> Self { Self::new() }
+}
+
+/// Deserializes a tree within the default `Limits`, for use with
+/// `#[serde(deserialize_with = "tt::limits::deserialize")]`.
+pub fn deserialize<'de, T: Deserialize<'de>, D: Deserializer<'de>>(deserializer: D) -> Result<Tree<T>, D::Error> {
+    Limits::new().deserialize(deserializer)
+}
+
+const FIELDS: &[&str] = &["label", "children"];
+
+struct TreeSeed<'a, T> {
+    limits: &'a Limits,
+    depth: usize,
+    nodes: &'a Cell<usize>,
+    label: PhantomData<T>
+}
+
+impl<'a, T> TreeSeed<'a, T> {
+    fn children(&self) -> ChildrenSeed<'a, T> {
+        ChildrenSeed(TreeSeed { limits: self.limits, depth: self.depth + 1, nodes: self.nodes, label: PhantomData })
+    }
+}
+
+impl<'a, 'de, T: Deserialize<'de>> DeserializeSeed<'de> for TreeSeed<'a, T> {
+    type Value = Tree<T>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        if self.depth > self.limits.max_depth {
+            return Err(de::Error::custom(format!("tree exceeds the maximum depth of {}", self.limits.max_depth)));
+        }
+        self.nodes.set(self.nodes.get() + 1);
+        if self.nodes.get() > self.limits.max_nodes {
+            return Err(de::Error::custom(format!("tree exceeds the maximum of {} nodes", self.limits.max_nodes)));
+        }
+
+        deserializer.deserialize_struct("Tree", FIELDS, self)
+    }
+}
+
+impl<'a, 'de, T: Deserialize<'de>> Visitor<'de> for TreeSeed<'a, T> {
+    type Value = Tree<T>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("struct Tree")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let label = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let children = seq.next_element_seed(self.children())?.ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        Ok(build(label, children))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut label = None;
+        let mut children = None;
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "label" if label.is_some()       => return Err(de::Error::duplicate_field("label")),
+                "label"                          => label = Some(map.next_value()?),
+                "children" if children.is_some() => return Err(de::Error::duplicate_field("children")),
+                "children"                       => children = Some(map.next_value_seed(self.children())?),
+                _                                => { map.next_value::<IgnoredAny>()?; }
+            }
+        }
+
+        let label = label.ok_or_else(|| de::Error::missing_field("label"))?;
+        let children = children.ok_or_else(|| de::Error::missing_field("children"))?;
+        Ok(build(label, children))
+    }
+}
+
+fn build<T>(label: T, children: Vec<Tree<T>>) -> Tree<T> {
+    let mut tree = Tree::new(label);
+    for child in children {
+        tree.attach_child(child);
+    }
+    tree
+}
+
+struct ChildrenSeed<'a, T>(TreeSeed<'a, T>);
+
+impl<'a, 'de, T: Deserialize<'de>> DeserializeSeed<'de> for ChildrenSeed<'a, T> {
+    type Value = Vec<Tree<T>>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'a, 'de, T: Deserialize<'de>> Visitor<'de> for ChildrenSeed<'a, T> {
+    type Value = Vec<Tree<T>>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a sequence of trees")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut children = Vec::new();
+        let seed = &self.0;
+        while let Some(child) = seq.next_element_seed(TreeSeed { label: PhantomData, ..*seed })? {
+            children.push(child);
+        }
+        Ok(children)
+    }
+}
@@ -0,0 +1,163 @@
+//! Three-way merges, combining two trees edited apart from a common base.
+//!
+//! Siblings are matched against the base like `diff` does. Changes made by only one side are
+//! taken, and subtrees added by either side are kept, those added alike by both sides only once.
+//! Siblings follow the order of ours, unless only theirs reordered the siblings of the base.
+
+use alloc::{vec, vec::Vec};
+
+use super::diff::matching;
+use super::tree::{Path, Tree};
+
+/// A change made by both sides that couldn't be merged, with the path of the subtree in the merged tree.
+/// * `Conflict::Label(at, ours, theirs)` - Both sides relabelled the subtree differently,
+///   the merged tree keeping ours.
+/// * `Conflict::Delete(at)` - One side deleted the subtree while the other changed it,
+///   the merged tree keeping the changed subtree.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Conflict<T> {
+    Label(Path, T, T),
+    Delete(Path)
+}
+
+/// A sibling of the merged tree.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Entry {
+    /// The sibling of the base at this index, kept by either side.
+    Base(usize),
+    /// The sibling of a side at this index, added by it.
+    New(usize, usize)
+}
+
+/// Merges the changes turning `base` into `ours` and into `theirs`, returning the merged tree
+/// along with the conflicts found on the way.
+/// # Example
+/// ```
+/// use tt::tree::Tree;
+/// use tt::merge::{merge3, Conflict};
+///
+/// let tree = |s: &str| Tree::<String>::from_sexpr(s).unwrap();
+/// let base = tree("(list (a) (b) (c))");
+/// let ours = tree("(list (a (x)) (c) (d))");
+/// let theirs = tree("(list (z) (b) (c) (e))");
+///
+/// let (merged, conflicts) = merge3(&base, &ours, &theirs);
+/// assert_eq!(merged, tree("(list (z (x)) (c) (d) (e))"));
+/// assert!(conflicts.is_empty());
+///
+/// let (merged, conflicts) = merge3(&base, &tree("(list (a) (b) (x))"), &tree("(list (a) (b) (y))"));
+/// assert_eq!(merged, tree("(list (a) (b) (x))"));
+/// assert_eq!(conflicts, vec![Conflict::Label(vec![2], String::from("x"), String::from("y"))]);
+///
+/// let theirs = tree("(list (a) (b (y)) (c))");
+/// let (merged, conflicts) = merge3(&base, &ours, &theirs);
+/// assert_eq!(merged, tree("(list (a (x)) (b (y)) (c) (d))"));
+/// assert_eq!(conflicts, vec![Conflict::Delete(vec![1])]);
+/// ```
+pub fn merge3<T: Clone + Eq>(base: &Tree<T>, ours: &Tree<T>, theirs: &Tree<T>) -> (Tree<T>, Vec<Conflict<T>>) {
+    let mut conflicts = Vec::new();
+    let merged = merge_at(&mut Path::new(), base, [ours, theirs], &mut conflicts);
+    (merged, conflicts)
+}
+
+/// Merges `base`, lying at `path` in the merged tree, with its versions of ours and of theirs.
+fn merge_at<T: Clone + Eq>(path: &mut Path, base: &Tree<T>, sides: [&Tree<T>; 2], conflicts: &mut Vec<Conflict<T>>) -> Tree<T> {
+    let [ours, theirs] = sides;
+    let label = if ours.label() == theirs.label() || theirs.label() == base.label() {
+        ours.label()
+    } else if ours.label() == base.label() {
+        theirs.label()
+    } else {
+        conflicts.push(Conflict::Label(path.clone(), ours.label().clone(), theirs.label().clone()));
+        ours.label()
+    };
+    let mut merged = Tree::new(label.clone());
+
+    let base = base.children_iter().as_slice();
+    let sides = [ours.children_iter().as_slice(), theirs.children_iter().as_slice()];
+    // For each side, the index of its version of each sibling of the base.
+    let mut versions = [vec![None; base.len()], vec![None; base.len()]];
+    for (side, children) in sides.iter().enumerate() {
+        for (j, i) in matching(base, children).into_iter().enumerate() {
+            if let Some(i) = i {
+                versions[side][i] = Some(j);
+            }
+        }
+    }
+
+    let reorders = |side: usize| {
+        let kept: Vec<usize> = versions[side].iter().flatten().copied().collect();
+        kept.windows(2).any(|w| w[0] > w[1])
+    };
+    let skeleton = if !reorders(0) && reorders(1) { 1 } else { 0 };
+    let other = 1 - skeleton;
+
+    let entries_of = |side: usize| -> Vec<Entry> {
+        let mut bases = vec![None; sides[side].len()];
+        for (i, j) in versions[side].iter().enumerate() {
+            if let Some(j) = j {
+                bases[*j] = Some(i);
+            }
+        }
+        bases.into_iter().enumerate().map(|(j, i)| i.map_or(Entry::New(side, j), Entry::Base)).collect()
+    };
+
+    // The siblings of the skeleton, with those of the other side woven in after their
+    // nearest preceding sibling of the base.
+    let mut entries = entries_of(skeleton);
+    let mut anchor = 0;
+    for entry in entries_of(other) {
+        match entry {
+            Entry::Base(i) => match entries.iter().position(|&e| e == entry) {
+                Some(p) => {
+                    anchor = p + 1;
+                    while matches!(entries.get(anchor), Some(Entry::New(s, _)) if *s == skeleton) {
+                        anchor += 1;
+                    }
+                }
+                None if sides[other][versions[other][i].unwrap()] != base[i] => {
+                    entries.insert(anchor, entry);
+                    anchor += 1;
+                }
+                None => {}
+            },
+            Entry::New(_, k) => {
+                let twin = entries.iter().position(|&e| matches!(e, Entry::New(s, j) if s == skeleton && sides[s][j] == sides[other][k]));
+                match twin {
+                    Some(p) => anchor = anchor.max(p + 1),
+                    None => {
+                        entries.insert(anchor, entry);
+                        anchor += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    for entry in entries {
+        let child = match entry {
+            Entry::New(side, j) => sides[side][j].clone(),
+            Entry::Base(i) => match (versions[0][i], versions[1][i]) {
+                (Some(j), Some(k)) => {
+                    path.push(merged.children());
+                    let child = merge_at(path, &base[i], [&sides[0][j], &sides[1][k]], conflicts);
+                    path.pop();
+                    child
+                }
+                (Some(j), None) | (None, Some(j)) => {
+                    let side = if versions[0][i].is_some() { 0 } else { 1 };
+                    if sides[side][j] == base[i] {
+                        continue;
+                    }
+                    let mut at = path.clone();
+                    at.push(merged.children());
+                    conflicts.push(Conflict::Delete(at));
+                    sides[side][j].clone()
+                }
+                (None, None) => continue
+            }
+        };
+        merged.attach_child(child);
+    }
+    merged
+}
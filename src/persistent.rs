@@ -0,0 +1,372 @@
+//! An immutable tree with structural sharing.
+//! Edits return a new tree sharing every unchanged subtree with the old one,
+//! so keeping many versions around only costs the nodes along each edited path.
+
+use alloc::collections::BTreeSet;
+use alloc::sync::Arc;
+use alloc::{vec, vec::Vec};
+use core::mem;
+use core::ops::Bound;
+
+use super::tree::{Path, Tree};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Node<T> {
+    label: T,
+    children: Vec<PersistentTree<T>>
+}
+
+/// An immutable, cheaply clonable tree.
+/// # Example
+/// ```
+/// use tt::persistent::PersistentTree;
+///
+/// let tree = PersistentTree::new(0).attach_child(&[], PersistentTree::new(1)).unwrap();
+/// let edited = tree.set_label(&[0], 2).unwrap();
+///
+/// assert_eq!(tree.get(&[0]).unwrap().label(), &1);
+/// assert_eq!(edited.get(&[0]).unwrap().label(), &2);
+///
+/// let grown = edited.attach_child(&[], PersistentTree::new(3)).unwrap();
+/// assert!(grown.child_at(0).unwrap().ptr_eq(edited.child_at(0).unwrap()));
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct PersistentTree<T>(Arc<Node<T>>);
+
+impl<T> Clone for PersistentTree<T> {
+    fn clone(&self) -> Self { PersistentTree(Arc::clone(&self.0)) }
+}
+
+impl<T> PersistentTree<T> {
+    /// Constructs and returns a new `PersistentTree`.
+    pub fn new(label: T) -> Self {
+        PersistentTree(Arc::new(Node { label, children: Vec::new() }))
+    }
+
+    pub fn label(&self) -> &T { &self.0.label }
+
+    /// Returns how many children this tree contains.
+    pub fn children(&self) -> usize { self.0.children.len() }
+    /// Returns whether this tree has no children.
+    pub fn is_leaf(&self) -> bool { self.0.children.is_empty() }
+
+    pub fn child_at(&self, i: usize) -> Option<&Self> { self.0.children.get(i) }
+
+    /// Returns the subtree reached by `path`.
+    /// Returns none if the path doesn't point to an existing point on the tree.
+    pub fn get(&self, path: &[usize]) -> Option<&Self> {
+        path.iter().try_fold(self, |tree, &i| tree.child_at(i))
+    }
+
+    /// Returns whether both trees are the very same shared node.
+    pub fn ptr_eq(&self, other: &Self) -> bool { Arc::ptr_eq(&self.0, &other.0) }
+
+    /// Constructs and returns a new `PersistentTree` with `label` and `children`.
+    pub(crate) fn from_parts(label: T, children: Vec<Self>) -> Self {
+        PersistentTree(Arc::new(Node { label, children }))
+    }
+}
+
+impl<T: Clone> PersistentTree<T> {
+    /// Returns a new tree with the label at `path` replaced by `label`.
+    /// Returns none if the path doesn't point to an existing point on the tree.
+    pub fn set_label(&self, path: &[usize], label: T) -> Option<Self> {
+        self.update(path, |node| {
+            node.label = label;
+            Some(())
+        })
+    }
+
+    /// Returns a new tree with `child` appended as the last child of the subtree at `path`.
+    /// Returns none if the path doesn't point to an existing point on the tree.
+    pub fn attach_child(&self, path: &[usize], child: Self) -> Option<Self> {
+        self.update(path, |node| {
+            node.children.push(child);
+            Some(())
+        })
+    }
+
+    /// Returns a new tree with `child` inserted at index `i` among the children of the subtree at `path`.
+    /// An index past the last child is clamped, appending the child.
+    /// Returns none if the path doesn't point to an existing point on the tree.
+    pub fn insert_child_at(&self, path: &[usize], i: usize, child: Self) -> Option<Self> {
+        self.update(path, |node| {
+            node.children.insert(i.min(node.children.len()), child);
+            Some(())
+        })
+    }
+
+    /// Returns a new tree without the child at index `i` of the subtree at `path`.
+    /// Returns none if there is no such child.
+    /// # Example
+    /// ```
+    /// use tt::persistent::PersistentTree;
+    ///
+    /// let tree = PersistentTree::new(0).attach_child(&[], PersistentTree::new(1)).unwrap();
+    /// assert!(tree.remove_child(&[], 1).is_none());
+    /// assert!(tree.remove_child(&[], 0).unwrap().is_leaf());
+    /// assert_eq!(tree.children(), 1);
+    /// ```
+    pub fn remove_child(&self, path: &[usize], i: usize) -> Option<Self> {
+        self.update(path, |node| {
+            if i < node.children.len() {
+                node.children.remove(i);
+                Some(())
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Returns a `Tree` holding copies of every label.
+    pub fn to_tree(&self) -> Tree<T> {
+        build(self, |tree| (tree.label().clone(), tree.0.children.iter()), |label, children| {
+            let mut tree = Tree::new(label);
+            for child in children {
+                tree.attach_child(child);
+            }
+            tree
+        })
+    }
+
+    fn update<F: FnOnce(&mut Node<T>) -> Option<()>>(&self, path: &[usize], f: F) -> Option<Self> {
+        let mut node = (*self.0).clone();
+        match path.split_first() {
+            Some((&i, rest)) => {
+                let child = node.children.get(i)?.update(rest, f)?;
+                node.children[i] = child;
+            }
+            None => f(&mut node)?
+        }
+        Some(PersistentTree(Arc::new(node)))
+    }
+}
+
+impl<T: Clone> Tree<T> {
+    /// Returns a frozen copy of the tree, unaffected by later edits of the original.
+    /// Every label is copied, on every call; `SnapshotTree` shares the subtrees left unchanged
+    /// between repeated snapshots.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    ///
+    /// let mut tree = Tree::new(0);
+    /// tree.create_subtree(1);
+    ///
+    /// let snapshot = tree.snapshot();
+    /// tree.child_at_mut(0).unwrap().set_label(2);
+    /// tree.create_subtree(3);
+    ///
+    /// assert_eq!(snapshot.children(), 1);
+    /// assert_eq!(snapshot.get(&[0]).unwrap().label(), &1);
+    /// ```
+    pub fn snapshot(&self) -> PersistentTree<T> {
+        build(self, |tree| (tree.label().clone(), tree.children_iter()), PersistentTree::from_parts)
+    }
+}
+
+/// A tree taking snapshots that share, with the previous one, every subtree left unchanged in between.
+/// It keeps the last snapshot along with the paths of the subtrees borrowed mutably since,
+/// and only copies those subtrees, and the labels of their ancestors, into the next snapshot.
+/// # Example
+/// ```
+/// use tt::tree::Tree;
+/// use tt::persistent::SnapshotTree;
+///
+/// let mut tree = Tree::new(0);
+/// tree.create_subtree(1);
+/// tree.create_subtree(2);
+/// let mut tree = SnapshotTree::from(tree);
+///
+/// let first = tree.snapshot();
+/// tree.get_mut(&[0]).unwrap().set_label(3);
+/// let second = tree.snapshot();
+///
+/// assert_eq!(first.get(&[0]).unwrap().label(), &1);
+/// assert_eq!(second.get(&[0]).unwrap().label(), &3);
+/// assert!(second.child_at(1).unwrap().ptr_eq(first.child_at(1).unwrap()));
+/// assert!(tree.snapshot().ptr_eq(&second));
+/// ```
+#[derive(Debug, Clone)]
+pub struct SnapshotTree<T> {
+    tree: Tree<T>,
+    last: Option<PersistentTree<T>>,
+    /// The paths of the subtrees borrowed mutably since the last snapshot, none within another.
+    edited: BTreeSet<Path>
+}
+
+impl<T> SnapshotTree<T> {
+    pub fn tree(&self) -> &Tree<T> { &self.tree }
+    pub fn into_tree(self) -> Tree<T> { self.tree }
+
+    /// Returns a mutable reference to the subtree reached by `path`, which the next snapshot copies.
+    /// Returns none if the path doesn't point to an existing point on the tree.
+    pub fn get_mut(&mut self, path: &[usize]) -> Option<&mut Tree<T>> {
+        let tree = self.tree.get_mut(path)?;
+        // Were one of them to hold `path`, it would be the last one up to `path`.
+        let within = self.edited.range::<[usize], _>((Bound::Unbounded, Bound::Included(path))).next_back();
+        if self.last.is_some() && !within.is_some_and(|edited| path.starts_with(edited)) {
+            self.edited.retain(|edited| !edited.starts_with(path));
+            self.edited.insert(path.to_vec());
+        }
+        Some(tree)
+    }
+}
+
+impl<T: Clone> SnapshotTree<T> {
+    /// Returns a frozen copy of the tree, unaffected by later edits.
+    pub fn snapshot(&mut self) -> PersistentTree<T> {
+        let snapshot = match &self.last {
+            Some(last) => {
+                let root = match self.edited.first() {
+                    None => Part::Kept(last),
+                    Some(edited) if edited.is_empty() => Part::Copied(&self.tree),
+                    Some(_) => Part::Patched(&self.tree, last, 0, self.edited.iter().collect())
+                };
+                build(root, Part::split, |label, children| {
+                    match label {
+                        Label::Kept(tree) => tree,
+                        Label::Copied(label) => PersistentTree::from_parts(label, children)
+                    }
+                })
+            }
+            None => self.tree.snapshot()
+        };
+        self.last = Some(snapshot.clone());
+        self.edited.clear();
+        snapshot
+    }
+}
+
+impl<T> From<Tree<T>> for SnapshotTree<T> {
+    fn from(tree: Tree<T>) -> Self {
+        SnapshotTree { tree, last: None, edited: BTreeSet::new() }
+    }
+}
+
+/// A subtree of the next snapshot, to be built from a `SnapshotTree` and its last snapshot.
+enum Part<'a, T> {
+    /// Unchanged since the last snapshot.
+    Kept(&'a PersistentTree<T>),
+    /// Borrowed mutably since the last snapshot.
+    Copied(&'a Tree<T>),
+    /// At the given depth, holding the subtrees borrowed mutably since the last snapshot at the given paths.
+    Patched(&'a Tree<T>, &'a PersistentTree<T>, usize, Vec<&'a Path>)
+}
+
+enum Label<T> {
+    Kept(PersistentTree<T>),
+    Copied(T)
+}
+
+impl<'a, T: Clone> Part<'a, T> {
+    /// Returns the label of the part, and its children unless it is kept as a whole.
+    fn split(self) -> (Label<T>, vec::IntoIter<Self>) {
+        match self {
+            Part::Kept(last) => (Label::Kept(last.clone()), Vec::new().into_iter()),
+            Part::Copied(tree) => {
+                let children: Vec<_> = tree.children_iter().map(Part::Copied).collect();
+                (Label::Copied(tree.label().clone()), children.into_iter())
+            }
+            Part::Patched(tree, last, depth, edited) => {
+                let mut edited = edited.into_iter().peekable();
+                let children: Vec<_> = (0..tree.children()).map(|i| {
+                    let (tree, last) = (tree.child_at(i).unwrap(), last.child_at(i).unwrap());
+                    let mut within = Vec::new();
+                    while let Some(path) = edited.next_if(|path| path[depth] == i) {
+                        within.push(path);
+                    }
+                    match within.first() {
+                        None => Part::Kept(last),
+                        Some(path) if path.len() == depth + 1 => Part::Copied(tree),
+                        Some(_) => Part::Patched(tree, last, depth + 1, within)
+                    }
+                }).collect();
+                (Label::Copied(tree.label().clone()), children.into_iter())
+            }
+        }
+    }
+}
+
+impl<T> From<Tree<T>> for PersistentTree<T> {
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    /// use tt::persistent::PersistentTree;
+    ///
+    /// let mut tree = Tree::new(0);
+    /// tree.create_subtree(1);
+    ///
+    /// let persistent = PersistentTree::from(tree.clone());
+    /// assert_eq!(persistent.to_tree(), tree);
+    /// ```
+    fn from(tree: Tree<T>) -> Self {
+        build(tree, |tree| {
+            let (label, children) = tree.into_parts();
+            (label, children.into_iter())
+        }, PersistentTree::from_parts)
+    }
+}
+
+/// Drops the tree iteratively, so that even very deep trees can't overflow the stack.
+/// Subtrees still shared with other trees are left to them.
+/// # Example
+/// ```
+/// use tt::tree::Tree;
+/// use tt::persistent::PersistentTree;
+///
+/// let mut tree = Tree::new(0);
+/// for i in 1..200_000 {
+///     let mut parent = Tree::new(i);
+///     parent.attach_child(tree);
+///     tree = parent;
+/// }
+/// let persistent = PersistentTree::from(tree);
+/// let copy = persistent.to_tree();
+/// assert_eq!(copy.height(), 199_999);
+/// drop(persistent);
+/// ```
+impl<T> Drop for PersistentTree<T> {
+    fn drop(&mut self) {
+        let mut stack = match Arc::get_mut(&mut self.0) {
+            Some(node) => mem::take(&mut node.children),
+            None => return
+        };
+        while let Some(mut tree) = stack.pop() {
+            if let Some(node) = Arc::get_mut(&mut tree.0) {
+                stack.append(&mut node.children);
+            }
+        }
+    }
+}
+
+/// Builds a tree bottom-up with an explicit stack, so that even very deep trees can't overflow it.
+/// `split` turns a node into its label and its children, and `join` puts a label together
+/// with its children, already built.
+fn build<S, L, I, U, F, G>(root: S, mut split: F, mut join: G) -> U
+where
+    I: Iterator<Item = S>,
+    F: FnMut(S) -> (L, I),
+    G: FnMut(L, Vec<U>) -> U
+{
+    let (label, children) = split(root);
+    let mut stack = vec![(label, children, Vec::new())];
+    loop {
+        let (_, children, _) = stack.last_mut().unwrap();
+        match children.next() {
+            Some(child) => {
+                let (label, children) = split(child);
+                let built = Vec::with_capacity(children.size_hint().0);
+                stack.push((label, children, built));
+            }
+            None => {
+                let (label, _, children) = stack.pop().unwrap();
+                let tree = join(label, children);
+                match stack.last_mut() {
+                    Some((_, _, siblings)) => siblings.push(tree),
+                    None => return tree
+                }
+            }
+        }
+    }
+}
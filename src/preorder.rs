@@ -0,0 +1,129 @@
+//! A flat serde representation of trees, writing every node in depth-first pre-order
+//! as a `[label, children]` pair, where `children` is the amount of its children.
+//!
+//! Unlike the derived and compact representations, neither serialization nor
+//! deserialization recurses, so arbitrarily deep trees can be written and read back.
+//! Either wrap a tree in `PreOrder`, or use this module with `#[serde(with = "tt::preorder")]`.
+//! # Example
+//! ```
+//! use serde::{Serialize, Deserialize};
+//! use tt::tree::Tree;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Document {
+//!     #[serde(with = "tt::preorder")]
+//!     syntax: Tree<String>
+//! }
+//! ```
+
+use alloc::{vec, vec::Vec};
+use core::fmt;
+use core::marker::PhantomData;
+
+use serde::de::{self, Deserializer, Visitor, SeqAccess};
+use serde::ser::{Serializer, SerializeSeq};
+use serde::{Serialize, Deserialize};
+
+use super::tree::Tree;
+
+/// A tree (de)serializing in the pre-order representation.
+/// # Example
+/// ```
+/// use serde::Deserialize;
+/// use serde::de::{IntoDeserializer, value::Error};
+/// use tt::tree::Tree;
+/// use tt::preorder::PreOrder;
+///
+/// // Every node is a `[label, children]` pair.
+/// let read = |nodes: Vec<Vec<u64>>| -> Result<Tree<u64>, Error> {
+///     PreOrder::deserialize(nodes.into_deserializer()).map(Tree::from)
+/// };
+///
+/// let tree = read(vec![vec![0, 2], vec![1, 1], vec![2, 0], vec![3, 0]]).unwrap();
+/// assert_eq!(tree.iter().collect::<Vec<_>>(), vec![&0, &1, &2, &3]);
+/// assert_eq!(tree.height(), 2);
+///
+/// // A chain far deeper than recursion allows.
+/// let tree = read((0..1_000_000).map(|i| vec![i, if i < 999_999 { 1 } else { 0 }]).collect()).unwrap();
+/// assert_eq!(tree.height(), 999_999);
+///
+/// // Missing and superfluous nodes.
+/// assert!(read(vec![vec![0, 1]]).is_err());
+/// assert!(read(vec![vec![0, 0], vec![1, 0]]).is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PreOrder<T>(pub Tree<T>);
+
+impl<T> From<Tree<T>> for PreOrder<T> {
+    fn from(tree: Tree<T>) -> Self { Self(tree) }
+}
+
+impl<T> From<PreOrder<T>> for Tree<T> {
+    fn from(preorder: PreOrder<T>) -> Self { preorder.0 }
+}
+
+impl<T: Serialize> Serialize for PreOrder<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize(&self.0, serializer)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for PreOrder<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize(deserializer).map(PreOrder)
+    }
+}
+
+/// Serializes `tree` in the pre-order representation.
+pub fn serialize<T: Serialize, S: Serializer>(tree: &Tree<T>, serializer: S) -> Result<S::Ok, S::Error> {
+    let mut seq = serializer.serialize_seq(Some(tree.size()))?;
+    let mut stack = vec![tree];
+    while let Some(tree) = stack.pop() {
+        seq.serialize_element(&(tree.label(), tree.children()))?;
+        stack.extend(tree.children_iter().rev());
+    }
+    seq.end()
+}
+
+/// Deserializes a tree from the pre-order representation.
+pub fn deserialize<'de, T: Deserialize<'de>, D: Deserializer<'de>>(deserializer: D) -> Result<Tree<T>, D::Error> {
+    deserializer.deserialize_seq(PreOrderVisitor(PhantomData))
+}
+
+struct PreOrderVisitor<T>(PhantomData<T>);
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for PreOrderVisitor<T> {
+    type Value = Tree<T>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a sequence of [label, children] pairs")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        // Trees still missing children, with how many they are missing.
+        let mut stack: Vec<(Tree<T>, usize)> = Vec::new();
+        let mut read = 0;
+
+        while let Some((label, children)) = seq.next_element::<(T, usize)>()? {
+            read += 1;
+            stack.push((Tree::new(label), children));
+            while let Some((_, 0)) = stack.last() {
+                let (tree, _) = stack.pop().unwrap();
+                match stack.last_mut() {
+                    Some((parent, missing)) => {
+                        parent.attach_child(tree);
+                        *missing -= 1;
+                    }
+                    None => {
+                        return match seq.next_element::<de::IgnoredAny>()? {
+                            None    => Ok(tree),
+                            Some(_) => Err(de::Error::custom("nodes after the end of the tree"))
+                        };
+                    }
+                }
+            }
+        }
+
+        Err(de::Error::invalid_length(read, &self))
+    }
+}
@@ -0,0 +1,135 @@
+use alloc::{boxed::Box, string::{String, ToString}};
+use core::fmt::{self, Display, Write};
+
+use super::tree::Tree;
+
+/// A configurable renderer of trees as indented text.
+/// # Example
+/// ```
+/// use tt::tree::Tree;
+/// use tt::printer::TreePrinter;
+///
+/// let mut tree = Tree::new("root");
+/// tree.create_subtree("alpha");
+/// tree.child_at_mut(0).unwrap().create_subtree("beta");
+/// tree.create_subtree("gamma");
+///
+/// let printer = TreePrinter::new().ascii(true).indent(3).truncate(3);
+/// assert_eq!(printer.print(&tree), "roo...\n|- alp...\n|  `- bet...\n`- gam...");
+///
+/// let printer = TreePrinter::with_formatter(|label: &&str| label.to_uppercase()).max_depth(1);
+/// assert_eq!(printer.print(&tree), "ROOT\n├── ALPHA\n└── GAMMA");
+/// ```
+pub struct TreePrinter<T> {
+    indent: usize,
+    ascii: bool,
+    max_depth: Option<usize>,
+    truncate: Option<usize>,
+    formatter: Box<dyn Fn(&T) -> String>
+}
+
+impl<T: Display> TreePrinter<T> {
+    /// Constructs and returns a new `TreePrinter` formatting labels with `Display`.
+    /// By default it indents by 4, uses Unicode connectors and has no depth
+    /// or label length limits.
+    pub fn new() -> Self {
+        Self::with_formatter(|label: &T| label.to_string())
+    }
+}
+
+impl<T: Display> Default for TreePrinter<T> {
+    fn default() -> Self { Self::new() }
+}
+
+impl<T> TreePrinter<T> {
+    /// Constructs and returns a new `TreePrinter` formatting labels with `formatter`.
+    pub fn with_formatter<F: Fn(&T) -> String + 'static>(formatter: F) -> Self {
+        Self {
+            indent: 4,
+            ascii: false,
+            max_depth: None,
+            truncate: None,
+            formatter: Box::new(formatter)
+        }
+    }
+
+    /// Sets the width of each level of indentation, at least 2.
+    pub fn indent(mut self, indent: usize) -> Self {
+        self.indent = indent.max(2);
+        self
+    }
+
+    /// Sets whether to use ASCII connectors instead of Unicode box drawing.
+    pub fn ascii(mut self, ascii: bool) -> Self {
+        self.ascii = ascii;
+        self
+    }
+
+    /// Sets the depth below which subtrees are omitted.
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Sets the amount of characters after which labels are cut off with an ellipsis.
+    pub fn truncate(mut self, truncate: usize) -> Self {
+        self.truncate = Some(truncate);
+        self
+    }
+
+    /// Sets the formatter used to turn labels into text.
+    pub fn formatter<F: Fn(&T) -> String + 'static>(mut self, formatter: F) -> Self {
+        self.formatter = Box::new(formatter);
+        self
+    }
+
+    /// Renders `tree` into a `String`.
+    pub fn print(&self, tree: &Tree<T>) -> String {
+        let mut out = String::new();
+        self.write(tree, &mut out).unwrap();
+        out
+    }
+
+    /// Renders `tree` into `out`.
+    pub fn write<W: Write>(&self, tree: &Tree<T>, out: &mut W) -> fmt::Result {
+        self.write_label(tree.label(), out)?;
+        self.write_children(tree, out, &mut String::new(), 1)
+    }
+
+    fn write_children<W: Write>(&self, tree: &Tree<T>, out: &mut W, prefix: &mut String, depth: usize) -> fmt::Result {
+        if self.max_depth.is_some_and(|max| depth > max) {
+            return Ok(());
+        }
+
+        let (branch, corner, vertical, line) = if self.ascii { ('|', '`', '|', '-') }
+                                               else { ('├', '└', '│', '─') };
+        for (i, child) in tree.children_iter().enumerate() {
+            let last = i + 1 == tree.children();
+            write!(out, "\n{}{}", prefix, if last { corner } else { branch })?;
+            for _ in 0..self.indent - 2 {
+                out.write_char(line)?;
+            }
+            out.write_char(' ')?;
+            self.write_label(child.label(), out)?;
+
+            let len = prefix.len();
+            prefix.push(if last { ' ' } else { vertical });
+            prefix.extend(core::iter::repeat_n(' ', self.indent - 1));
+            self.write_children(child, out, prefix, depth + 1)?;
+            prefix.truncate(len);
+        }
+
+        Ok(())
+    }
+
+    fn write_label<W: Write>(&self, label: &T, out: &mut W) -> fmt::Result {
+        let label = (self.formatter)(label);
+        match self.truncate {
+            Some(max) if label.chars().count() > max => {
+                out.write_str(&label.chars().take(max).collect::<String>())?;
+                out.write_str(if self.ascii { "..." } else { "…" })
+            }
+            _ => out.write_str(&label)
+        }
+    }
+}
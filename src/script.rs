@@ -0,0 +1,235 @@
+//! A line-based editing language for automating changes to a `Focus`.
+//!
+//! Each line holds one command; blank lines and lines starting with `#` are skipped:
+//! * `up`, `down`, `root`, `next`, `prev` - Jump like the matching `Jump`
+//! * `go <movement>` - Jump according to a movement command, see `command`
+//! * `mark <name>` / `goto <name>` - Mark the focused subtree / jump to a mark
+//! * `add <label>` - Create a new child subtree, moving the focus to it
+//! * `before <label>` / `after <label>` - Create a new sibling subtree, moving the focus to it
+//! * `set <label>` - Replace the label of the focused subtree
+//! * `delete` - Delete the focused subtree
+//! * `move <movement>` - Move the focused subtree under the subtree the movement leads to
+//!
+//! Labels are either bare words or quoted with `"`, a `\` escaping the following
+//! character, with `\n` standing for a newline.
+//! # Example
+//! ```
+//! use tt::focus::{Focus, JumpError};
+//! use tt::script::{self, ScriptError};
+//!
+//! let mut focus = Focus::new(String::from("root"));
+//! script::run(&mut focus, r#"
+//!     ## Build an outline.
+//!     add "first item"
+//!     after second
+//!     add nested
+//!     move ../../0
+//! "#).unwrap();
+//! assert_eq!(focus.labels(), vec!["root", "first item", "nested"]);
+//! assert_eq!(focus.tree().iter().collect::<Vec<_>>(), vec!["root", "first item", "nested", "second"]);
+//!
+//! let error = script::run(&mut focus, "root\nup").unwrap_err();
+//! assert_eq!(error, ScriptError::Jump { line: 2, error: JumpError::AtRoot });
+//! assert_eq!(error.to_string(), "2: the focus is at the root");
+//!
+//! let error = script::run(&mut focus, "add 1\nfly").unwrap_err();
+//! assert_eq!(error.to_string(), "2:1: unknown command `fly`");
+//! assert_eq!(focus.tree().size(), 4);
+//! ```
+
+use alloc::{string::{String, ToString}, vec, vec::Vec, format};
+use core::error::Error;
+use core::fmt;
+use core::str::FromStr;
+
+use super::command;
+use super::focus::{Focus, Jump, JumpError};
+use super::format::{Cursor, ParseError};
+
+/// The reason a script couldn't be run.
+/// * `ScriptError::Parse(e)` - The script isn't well-formed, nothing was applied.
+/// * `ScriptError::Jump { line, error }` - The command at `line` failed, the ones before it were applied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScriptError {
+    Parse(ParseError),
+    Jump { line: usize, error: JumpError }
+}
+
+impl ScriptError {
+    /// Returns the line, starting from 1, at which the error occurred.
+    pub fn line(&self) -> usize {
+        match self {
+            ScriptError::Parse(e)          => e.line(),
+            ScriptError::Jump { line, .. } => *line
+        }
+    }
+}
+
+impl fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScriptError::Parse(e)              => e.fmt(f),
+            ScriptError::Jump { line, error } => write!(f, "{}: {}", line, error)
+        }
+    }
+}
+
+impl Error for ScriptError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ScriptError::Parse(e)           => Some(e),
+            ScriptError::Jump { error, .. } => Some(error)
+        }
+    }
+}
+
+impl From<ParseError> for ScriptError {
+    fn from(e: ParseError) -> Self { ScriptError::Parse(e) }
+}
+
+enum Command<T> {
+    Jump(Vec<Jump>),
+    Move(Vec<Jump>),
+    Mark(String),
+    Goto(String),
+    Add(T),
+    Before(T),
+    After(T),
+    Set(T),
+    Delete
+}
+
+/// Parses `script` and runs its commands against `focus` in order.
+///
+/// Returns `ScriptError::Parse`, leaving the focus unchanged, if any line isn't well-formed.
+/// Returns `ScriptError::Jump` if a command fails, stopping right before it;
+/// run the script within `Focus::transaction` to revert the commands applied until then.
+pub fn run<T: FromStr>(focus: &mut Focus<T>, script: &str) -> Result<(), ScriptError>
+where
+    T::Err: fmt::Display
+{
+    let mut commands = Vec::new();
+    for (i, s) in script.lines().enumerate() {
+        if let Some(command) = parse_line(i + 1, s)? {
+            commands.push((i + 1, command));
+        }
+    }
+
+    for (line, command) in commands {
+        apply(focus, command).map_err(|error| ScriptError::Jump { line, error })?;
+    }
+    Ok(())
+}
+
+fn apply<T>(focus: &mut Focus<T>, command: Command<T>) -> Result<(), JumpError> {
+    match command {
+        Command::Jump(jumps) => focus.jump_all(jumps)?,
+        Command::Move(jumps) => {
+            let dest = focus.resolve(jumps)?;
+            focus.move_focused_to(dest)?;
+        }
+        Command::Mark(name)    => focus.set_mark(name),
+        Command::Goto(name)    => focus.jump_to_mark(&name)?,
+        Command::Add(label)    => focus.create_subtree(label),
+        Command::Before(label) => if !focus.insert_sibling_before(label) { return Err(JumpError::AtRoot) },
+        Command::After(label)  => if !focus.insert_sibling_after(label) { return Err(JumpError::AtRoot) },
+        Command::Set(label)    => { focus.set_label(label); }
+        Command::Delete        => { focus.delete_focused().ok_or(JumpError::AtRoot)?; }
+    }
+    Ok(())
+}
+
+/// Parses the line `s`, numbered `line`, returning none if it is blank or a comment.
+fn parse_line<T: FromStr>(line: usize, s: &str) -> Result<Option<Command<T>>, ParseError>
+where
+    T::Err: fmt::Display
+{
+    let mut cursor = Cursor::new(s);
+    let (_, column) = cursor.position();
+    if matches!(cursor.peek(), None | Some('#')) {
+        return Ok(None);
+    }
+
+    let command = match word(&mut cursor).as_str() {
+        "up"     => Command::Jump(vec![Jump::Up]),
+        "down"   => Command::Jump(vec![Jump::Down]),
+        "root"   => Command::Jump(vec![Jump::Root]),
+        "next"   => Command::Jump(vec![Jump::Next]),
+        "prev"   => Command::Jump(vec![Jump::Prev]),
+        "go"     => Command::Jump(movement(line, &mut cursor)?),
+        "move"   => Command::Move(movement(line, &mut cursor)?),
+        "mark"   => Command::Mark(name(line, &mut cursor)?),
+        "goto"   => Command::Goto(name(line, &mut cursor)?),
+        "add"    => Command::Add(label(line, &mut cursor)?),
+        "before" => Command::Before(label(line, &mut cursor)?),
+        "after"  => Command::After(label(line, &mut cursor)?),
+        "set"    => Command::Set(label(line, &mut cursor)?),
+        "delete" => Command::Delete,
+        keyword  => return Err(ParseError::new(line, column, format!("unknown command `{}`", keyword)))
+    };
+
+    let (_, column) = cursor.position();
+    match cursor.peek() {
+        None => Ok(Some(command)),
+        Some(_) => Err(ParseError::new(line, column, "expected the end of the line"))
+    }
+}
+
+/// Parses the rest of the line as a movement command.
+fn movement(line: usize, cursor: &mut Cursor) -> Result<Vec<Jump>, ParseError> {
+    let (_, column) = cursor.position();
+    let rest: String = core::iter::from_fn(|| cursor.bump()).collect();
+    if rest.is_empty() {
+        return Err(ParseError::new(line, column, "expected a movement"));
+    }
+    command::parse(&rest).map_err(|e| ParseError::new(line, column + e.column() - 1, e.message()))
+}
+
+/// Parses a mark name, a bare word.
+fn name(line: usize, cursor: &mut Cursor) -> Result<String, ParseError> {
+    let (_, column) = cursor.position();
+    match word(cursor) {
+        name if name.is_empty() => Err(ParseError::new(line, column, "expected a mark name")),
+        name => Ok(name)
+    }
+}
+
+/// Parses a label, either a bare word or a quoted string.
+fn label<T: FromStr>(line: usize, cursor: &mut Cursor) -> Result<T, ParseError>
+where
+    T::Err: fmt::Display
+{
+    let (_, column) = cursor.position();
+    let text = if cursor.peek() == Some('"') {
+        cursor.bump();
+        let mut text = String::new();
+        loop {
+            match cursor.bump() {
+                Some('"') => break text,
+                Some('\\') => match cursor.bump() {
+                    Some('n') => text.push('\n'),
+                    Some(c) => text.push(c),
+                    None => return Err(ParseError::new(line, column, "unterminated string"))
+                },
+                Some(c) => text.push(c),
+                None => return Err(ParseError::new(line, column, "unterminated string"))
+            }
+        }
+    } else {
+        match word(cursor) {
+            text if text.is_empty() => return Err(ParseError::new(line, column, "expected a label")),
+            text => text
+        }
+    };
+    text.parse::<T>().map_err(|e| ParseError::new(line, column, e.to_string()))
+}
+
+/// Parses characters up to the next whitespace.
+fn word(cursor: &mut Cursor) -> String {
+    let mut word = String::new();
+    while let Some(c) = cursor.peek().filter(|c| !c.is_whitespace()) {
+        word.push(c);
+        cursor.bump();
+    }
+    word
+}
@@ -1,5 +1,20 @@
+use alloc::collections::BTreeMap;
+use alloc::{vec, vec::Vec};
+use core::cmp::Ordering;
+use core::fmt;
+use core::mem::{self, ManuallyDrop};
+use core::ops::{Index, IndexMut};
+use core::ptr;
+
+#[cfg(feature = "serde")]
 use serde::{Serialize, Deserialize};
 
+use super::printer::TreePrinter;
+use super::iter::{Iter, IterMut, Bfs, PostIter, PathIter, Leaves, LeafPaths};
+
+/// A path to a point within a tree.
+pub type Path = Vec<usize>;
+
 /// An infinitely branching tree.
 /// # Example
 /// ```
@@ -11,9 +26,12 @@ use serde::{Serialize, Deserialize};
 ///
 /// tree.create_subtree(1);
 /// assert_eq!(tree.children(), 1);
-/// 
+///
+/// let copy = tree.clone();
+/// assert_eq!(copy, tree);
 /// ```
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Tree<T> {
     label: T,
     children: Vec<Tree<T>>
@@ -28,14 +46,805 @@ impl<T> Tree<T> {
         }
     }
 
+    /// Constructs and returns a new `Tree` grown from `seed`.
+    /// `f` turns each seed into a label and the seeds of its children.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    ///
+    /// // Binary representation of numbers below 8.
+    /// let tree = Tree::unfold(1, |n| (n, [n * 2, n * 2 + 1].iter().copied().filter(|c| *c < 8).collect()));
+    /// assert_eq!(tree.iter().collect::<Vec<_>>(), vec![&1, &2, &4, &5, &3, &6, &7]);
+    /// ```
+    pub fn unfold<S, F: FnMut(S) -> (T, Vec<S>)>(seed: S, mut f: F) -> Self {
+        Self::unfold_with(seed, None, &mut f)
+    }
+
+    /// Constructs and returns a new `Tree` grown from `seed`, no deeper than `max_depth`.
+    /// Seeds produced for children beyond `max_depth` are discarded.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    ///
+    /// // An infinite tree, cut off.
+    /// let tree = Tree::unfold_to_depth(0, 2, |n| (n, vec![n + 1, n + 1]));
+    /// assert_eq!(tree.height(), 2);
+    /// assert_eq!(tree.size(), 7);
+    /// ```
+    pub fn unfold_to_depth<S, F: FnMut(S) -> (T, Vec<S>)>(seed: S, max_depth: usize, mut f: F) -> Self {
+        Self::unfold_with(seed, Some(max_depth), &mut f)
+    }
+
+    fn unfold_with<S, F: FnMut(S) -> (T, Vec<S>)>(seed: S, max_depth: Option<usize>, f: &mut F) -> Self {
+        let (label, seeds) = f(seed);
+        let children = match max_depth {
+            Some(0) => Vec::new(),
+            _       => seeds.into_iter().map(|s| Self::unfold_with(s, max_depth.map(|d| d - 1), f)).collect()
+        };
+
+        Self { label, children }
+    }
+
     pub fn label(&self) -> &T { &self.label }
+    pub fn label_mut(&mut self) -> &mut T { &mut self.label }
+
+    /// Replaces the label, returning the old one.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    ///
+    /// let mut tree = Tree::new(0);
+    /// assert_eq!(tree.set_label(1), 0);
+    /// *tree.label_mut() += 1;
+    /// assert_eq!(tree.label(), &2);
+    /// ```
+    pub fn set_label(&mut self, label: T) -> T {
+        mem::replace(&mut self.label, label)
+    }
+
     /// Returns how many children this tree contains.
     pub fn children(&self) -> usize { self.children.len() }
+    /// Returns whether this tree has no children.
+    pub fn is_leaf(&self) -> bool { self.children.is_empty() }
 
     pub fn child_at(&self, i: usize) -> Option<&Self> { self.children.get(i) }
     pub fn child_at_mut(&mut self, i: usize) -> Option<&mut Self> { self.children.get_mut(i) }
 
+    /// Returns the subtree reached by `path`.
+    /// Returns none if the path doesn't point to an existing point on the tree.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    ///
+    /// let mut tree = Tree::new(0);
+    /// tree.create_subtree(1);
+    /// tree.child_at_mut(0).unwrap().create_subtree(2);
+    ///
+    /// assert_eq!(tree.get(&vec![0, 0]).unwrap().label(), &2);
+    /// assert!(tree.get(&vec![1]).is_none());
+    ///
+    /// tree.get_mut(&vec![0]).unwrap().set_label(3);
+    /// assert_eq!(tree.get(&vec![0]).unwrap().label(), &3);
+    /// ```
+    pub fn get(&self, path: &[usize]) -> Option<&Self> {
+        path.iter().try_fold(self, |tree, i| tree.child_at(*i))
+    }
+
+    /// Returns a mutable reference to the subtree reached by `path`.
+    /// Returns none if the path doesn't point to an existing point on the tree.
+    pub fn get_mut(&mut self, path: &[usize]) -> Option<&mut Self> {
+        path.iter().try_fold(self, |tree, i| tree.child_at_mut(*i))
+    }
+
+    /// Returns whether `path` points to an existing point on the tree.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    ///
+    /// let mut tree = Tree::new(0);
+    /// tree.create_subtree(1);
+    ///
+    /// assert!(tree.contains_path(&vec![]));
+    /// assert!(tree.contains_path(&vec![0]));
+    /// assert!(!tree.contains_path(&vec![0, 0]));
+    /// ```
+    pub fn contains_path(&self, path: &[usize]) -> bool { self.get(path).is_some() }
+
+    /// Returns an iterator over the direct children.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    ///
+    /// let mut tree = Tree::new(0);
+    /// tree.create_subtree(1);
+    /// tree.create_subtree(2);
+    ///
+    /// for child in tree.children_iter_mut() {
+    ///     *child.label_mut() *= 10;
+    /// }
+    /// let labels: Vec<_> = tree.children_iter().map(|c| c.label()).collect();
+    /// assert_eq!(labels, vec![&10, &20]);
+    /// ```
+    pub fn children_iter(&self) -> core::slice::Iter<'_, Self> { self.children.iter() }
+    /// Returns an iterator over mutable references to the direct children.
+    pub fn children_iter_mut(&mut self) -> core::slice::IterMut<'_, Self> { self.children.iter_mut() }
+
+    /// Returns a depth-first pre-order iterator over all labels.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    ///
+    /// let mut tree = Tree::new(0);
+    /// tree.create_subtree(1);
+    /// tree.child_at_mut(0).unwrap().create_subtree(2);
+    /// tree.create_subtree(3);
+    ///
+    /// assert_eq!(tree.iter().collect::<Vec<_>>(), vec![&0, &1, &2, &3]);
+    /// assert_eq!(tree.iter().sum::<i32>(), 6);
+    /// ```
+    pub fn iter(&self) -> Iter<'_, T> { Iter::new(self) }
+    /// Returns a depth-first pre-order iterator over mutable references to all labels.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    ///
+    /// let mut tree = Tree::new(0);
+    /// tree.create_subtree(1);
+    /// tree.child_at_mut(0).unwrap().create_subtree(2);
+    ///
+    /// for (id, label) in tree.iter_mut().enumerate() {
+    ///     *label = id * 10;
+    /// }
+    /// assert_eq!(tree.iter().collect::<Vec<_>>(), vec![&0, &10, &20]);
+    /// ```
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> { IterMut::new(self) }
+
+    /// Returns a depth-first post-order iterator over all labels.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    ///
+    /// let mut tree = Tree::new(0);
+    /// tree.create_subtree(1);
+    /// tree.child_at_mut(0).unwrap().create_subtree(2);
+    /// tree.create_subtree(3);
+    ///
+    /// assert_eq!(tree.iter_post().collect::<Vec<_>>(), vec![&2, &1, &3, &0]);
+    /// ```
+    pub fn iter_post(&self) -> PostIter<'_, T> { PostIter::new(self) }
+
+    /// Returns a depth-first pre-order iterator over all labels, each paired with its path.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    /// use tt::focus::Focus;
+    ///
+    /// let mut tree = Tree::new(0);
+    /// tree.create_subtree(1);
+    /// tree.child_at_mut(0).unwrap().create_subtree(2);
+    ///
+    /// let paths: Vec<_> = tree.iter_paths().collect();
+    /// assert_eq!(paths, vec![(vec![], &0), (vec![0], &1), (vec![0, 0], &2)]);
+    ///
+    /// let path = paths[2].0.clone();
+    /// let focus = Focus::from(tree, Some(path)).unwrap();
+    /// assert_eq!(focus.focused().label(), &2);
+    /// ```
+    pub fn iter_paths(&self) -> PathIter<'_, T> { PathIter::new(self) }
+
+    /// Returns a depth-first iterator over the labels of all leaves.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    ///
+    /// let mut tree = Tree::new(0);
+    /// tree.create_subtree(1);
+    /// tree.child_at_mut(0).unwrap().create_subtree(2);
+    /// tree.create_subtree(3);
+    ///
+    /// assert!(!tree.is_leaf());
+    /// assert_eq!(tree.leaves().collect::<Vec<_>>(), vec![&2, &3]);
+    /// assert_eq!(tree.leaf_paths().collect::<Vec<_>>(), vec![(vec![0, 0], &2), (vec![1], &3)]);
+    /// ```
+    pub fn leaves(&self) -> Leaves<'_, T> { Leaves::new(self) }
+    /// Returns a depth-first iterator over the labels of all leaves, each paired with its path.
+    pub fn leaf_paths(&self) -> LeafPaths<'_, T> { LeafPaths::new(self) }
+
+    /// Returns a breadth-first iterator over all labels.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    ///
+    /// let mut tree = Tree::new(0);
+    /// tree.create_subtree(1);
+    /// tree.child_at_mut(0).unwrap().create_subtree(3);
+    /// tree.create_subtree(2);
+    ///
+    /// assert_eq!(tree.iter_bfs().collect::<Vec<_>>(), vec![&0, &1, &2, &3]);
+    /// ```
+    pub fn iter_bfs(&self) -> Bfs<'_, T> { Bfs::new(self) }
+
+    /// Returns all labels grouped by depth, starting with the root.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    ///
+    /// let mut tree = Tree::new(0);
+    /// tree.create_subtree(1);
+    /// tree.child_at_mut(0).unwrap().create_subtree(3);
+    /// tree.create_subtree(2);
+    ///
+    /// assert_eq!(tree.levels(), vec![vec![&0], vec![&1, &2], vec![&3]]);
+    /// ```
+    pub fn levels(&self) -> Vec<Vec<&T>> {
+        let mut levels = Vec::new();
+        let mut level = vec![self];
+        while !level.is_empty() {
+            levels.push(level.iter().map(|t| t.label()).collect());
+            level = level.iter().flat_map(|t| t.children_iter()).collect();
+        }
+        levels
+    }
+
+    /// Returns mutable references to the label and the children at once.
+    pub(crate) fn split_mut(&mut self) -> (&mut T, &mut Vec<Self>) {
+        (&mut self.label, &mut self.children)
+    }
+
+    /// Consumes the tree, returning its label and children.
+    pub(crate) fn into_parts(self) -> (T, Vec<Self>) {
+        let mut tree = ManuallyDrop::new(self);
+        let children = mem::take(&mut tree.children);
+        // SAFETY: `tree` is never dropped nor used again, so the label is moved out exactly once.
+        let label = unsafe { ptr::read(&tree.label) };
+        (label, children)
+    }
+
+    /// Returns the total number of nodes in the tree, including the root.
+    pub fn size(&self) -> usize { self.iter().count() }
+
+    /// Returns the maximum depth of the tree, a lone root having height `0`.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    ///
+    /// let mut tree = Tree::new(0);
+    /// assert_eq!(tree.height(), 0);
+    ///
+    /// tree.create_subtree(1);
+    /// tree.child_at_mut(0).unwrap().create_subtree(2);
+    /// tree.create_subtree(3);
+    /// assert_eq!(tree.size(), 4);
+    /// assert_eq!(tree.height(), 2);
+    /// ```
+    pub fn height(&self) -> usize {
+        let mut height = 0;
+        let mut stack = vec![(self, 0)];
+        while let Some((tree, depth)) = stack.pop() {
+            height = height.max(depth);
+            stack.extend(tree.children_iter().map(|c| (c, depth + 1)));
+        }
+        height
+    }
+
+    /// Returns the depth of the point reached by `path`.
+    /// Returns none if the path doesn't point to an existing point on the tree.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    ///
+    /// let mut tree = Tree::new(0);
+    /// tree.create_subtree(1);
+    ///
+    /// assert_eq!(tree.depth_of(&vec![]), Some(0));
+    /// assert_eq!(tree.depth_of(&vec![0]), Some(1));
+    /// assert_eq!(tree.depth_of(&vec![1]), None);
+    /// ```
+    pub fn depth_of(&self, path: &[usize]) -> Option<usize> {
+        self.get(path).map(|_| path.len())
+    }
+
+    /// Returns the path to the first label, in depth-first pre-order, that satisfies `f`.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    /// use tt::focus::Focus;
+    ///
+    /// let mut tree = Tree::new(0);
+    /// tree.create_subtree(1);
+    /// tree.child_at_mut(0).unwrap().create_subtree(2);
+    /// tree.create_subtree(2);
+    ///
+    /// let path = tree.find(|label| *label == 2).unwrap();
+    /// assert_eq!(path, vec![0, 0]);
+    /// assert!(tree.find(|label| *label == 3).is_none());
+    ///
+    /// let focus = Focus::from(tree, Some(path)).unwrap();
+    /// assert_eq!(focus.focused().label(), &2);
+    /// ```
+    pub fn find<F: FnMut(&T) -> bool>(&self, mut f: F) -> Option<Path> {
+        self.iter_paths().find(|(_, label)| f(label)).map(|(path, _)| path)
+    }
+
+    /// Returns the paths to all labels that satisfy `f`, in depth-first pre-order.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    ///
+    /// let mut tree = Tree::new("urgent");
+    /// tree.create_subtree("later");
+    /// tree.child_at_mut(0).unwrap().create_subtree("urgent");
+    /// tree.create_subtree("urgent");
+    ///
+    /// assert_eq!(tree.find_all(|label| *label == "urgent"), vec![vec![], vec![0, 0], vec![1]]);
+    /// ```
+    pub fn find_all<F: FnMut(&T) -> bool>(&self, mut f: F) -> Vec<Path> {
+        self.iter_paths().filter(|(_, label)| f(label)).map(|(path, _)| path).collect()
+    }
+
+    /// Consumes the tree and returns a tree of the same shape, with every label
+    /// transformed by `f` in depth-first pre-order.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    ///
+    /// let mut tree = Tree::new("1");
+    /// tree.create_subtree("2");
+    ///
+    /// let tree = tree.map(|label| label.parse::<i32>().unwrap());
+    /// assert_eq!(tree.iter().collect::<Vec<_>>(), vec![&1, &2]);
+    /// ```
+    pub fn map<U, F: FnMut(T) -> U>(self, mut f: F) -> Tree<U> {
+        self.map_with(&mut f)
+    }
+
+    fn map_with<U, F: FnMut(T) -> U>(self, f: &mut F) -> Tree<U> {
+        let (label, children) = self.into_parts();
+        Tree {
+            label: f(label),
+            children: children.into_iter().map(|c| c.map_with(f)).collect()
+        }
+    }
+
+    /// Consumes the tree and returns a tree of the same shape, with every label
+    /// transformed by the fallible `f` in depth-first pre-order.
+    /// Returns the first error produced by `f`, without visiting the remaining labels.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    ///
+    /// let mut tree = Tree::new("1");
+    /// tree.create_subtree("2");
+    /// let parsed = tree.try_map(|label| label.parse::<i32>());
+    /// assert_eq!(parsed.unwrap().iter().collect::<Vec<_>>(), vec![&1, &2]);
+    ///
+    /// let mut tree = Tree::new("1");
+    /// tree.create_subtree("x");
+    /// assert!(tree.try_map(|label| label.parse::<i32>()).is_err());
+    /// ```
+    pub fn try_map<U, E, F: FnMut(T) -> Result<U, E>>(self, mut f: F) -> Result<Tree<U>, E> {
+        self.try_map_with(&mut f)
+    }
+
+    fn try_map_with<U, E, F: FnMut(T) -> Result<U, E>>(self, f: &mut F) -> Result<Tree<U>, E> {
+        let (label, children) = self.into_parts();
+        Ok(Tree {
+            label: f(label)?,
+            children: children.into_iter().map(|c| c.try_map_with(f)).collect::<Result<_, _>>()?
+        })
+    }
+
+    /// Returns a tree of the same shape, with every label transformed by `f` in
+    /// depth-first pre-order.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    ///
+    /// let mut tree = Tree::new(String::from("root"));
+    /// tree.create_subtree(String::from("a"));
+    ///
+    /// let lengths = tree.map_ref(|label| label.len());
+    /// assert_eq!(lengths.iter().collect::<Vec<_>>(), vec![&4, &1]);
+    /// ```
+    pub fn map_ref<U, F: FnMut(&T) -> U>(&self, mut f: F) -> Tree<U> {
+        self.map_ref_with(&mut f)
+    }
+
+    fn map_ref_with<U, F: FnMut(&T) -> U>(&self, f: &mut F) -> Tree<U> {
+        Tree {
+            label: f(&self.label),
+            children: self.children.iter().map(|c| c.map_ref_with(f)).collect()
+        }
+    }
+
+    /// Consumes two trees of the same shape, returning a tree of label pairs.
+    /// Returns none if the shapes differ.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    ///
+    /// let mut values = Tree::new(1);
+    /// values.create_subtree(2);
+    /// let mut notes = Tree::new("one");
+    /// notes.create_subtree("two");
+    ///
+    /// let zipped = values.zip(notes).unwrap();
+    /// assert_eq!(zipped.iter().collect::<Vec<_>>(), vec![&(1, "one"), &(2, "two")]);
+    ///
+    /// assert!(Tree::new(1).zip(Tree::unfold_to_depth(0, 1, |n| (n, vec![n]))).is_none());
+    /// ```
+    pub fn zip<U>(self, other: Tree<U>) -> Option<Tree<(T, U)>> {
+        self.zip_with(other, |a, b| (a, b))
+    }
+
+    /// Consumes two trees of the same shape, combining their labels with `f`.
+    /// Returns none, without calling `f`, if the shapes differ.
+    pub fn zip_with<U, V, F: FnMut(T, U) -> V>(self, other: Tree<U>, mut f: F) -> Option<Tree<V>> {
+        if self.isomorphic_to(&other) {
+            Some(self.zip_with_unchecked(other, &mut f))
+        } else {
+            None
+        }
+    }
+
+    fn zip_with_unchecked<U, V, F: FnMut(T, U) -> V>(self, other: Tree<U>, f: &mut F) -> Tree<V> {
+        let (a, a_children) = self.into_parts();
+        let (b, b_children) = other.into_parts();
+        Tree {
+            label: f(a, b),
+            children: a_children.into_iter().zip(b_children).map(|(a, b)| a.zip_with_unchecked(b, f)).collect()
+        }
+    }
+
+    /// Returns whether both trees have the same shape, ignoring labels.
+    /// Children are compared in order, so mirrored shapes differ; `eq_unordered` is the comparison ignoring child order.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    ///
+    /// let tree = Tree::<String>::from_sexpr("(a (b (c)) (d))").unwrap();
+    /// assert!(tree.isomorphic_to(&Tree::<String>::from_sexpr("(x (y (z)) (w))").unwrap()));
+    /// assert!(!tree.isomorphic_to(&Tree::<String>::from_sexpr("(a (d) (b (c)))").unwrap()));
+    /// ```
+    pub fn isomorphic_to<U>(&self, other: &Tree<U>) -> bool {
+        self.children() == other.children()
+            && self.children.iter().zip(other.children_iter()).all(|(a, b)| a.isomorphic_to(b))
+    }
+
+    /// Folds the tree bottom-up, combining each label with the folded results of its children.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    ///
+    /// let mut tree = Tree::new("+");
+    /// tree.create_subtree("1");
+    /// tree.create_subtree("*");
+    /// tree.child_at_mut(1).unwrap().create_subtree("2");
+    /// tree.child_at_mut(1).unwrap().create_subtree("3");
+    ///
+    /// let value = tree.fold(|label, children: Vec<i32>| match *label {
+    ///     "+" => children.iter().sum(),
+    ///     "*" => children.iter().product(),
+    ///     n   => n.parse().unwrap()
+    /// });
+    /// assert_eq!(value, 7);
+    ///
+    /// let sexpr = tree.fold(|label, children: Vec<String>| {
+    ///     std::iter::once(label.to_string()).chain(children).collect::<Vec<_>>().join(" ")
+    /// });
+    /// assert_eq!(sexpr, "+ 1 * 2 3");
+    /// ```
+    pub fn fold<R, F: FnMut(&T, Vec<R>) -> R>(&self, mut f: F) -> R {
+        self.fold_with(&mut f)
+    }
+
+    fn fold_with<R, F: FnMut(&T, Vec<R>) -> R>(&self, f: &mut F) -> R {
+        let children = self.children.iter().map(|c| c.fold_with(f)).collect();
+        f(&self.label, children)
+    }
+
     pub fn create_subtree(&mut self, label: T) {
-        self.children.push(Tree::new(label));
+        self.attach_child(Tree::new(label));
+    }
+
+    /// Appends an existing `child` tree as the last child.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    ///
+    /// let mut branch = Tree::new(1);
+    /// branch.create_subtree(2);
+    ///
+    /// let mut tree = Tree::new(0);
+    /// tree.attach_child(branch);
+    /// assert_eq!(tree.children(), 1);
+    /// assert_eq!(tree.child_at(0).unwrap().children(), 1);
+    /// ```
+    pub fn attach_child(&mut self, child: Self) {
+        self.children.push(child);
+    }
+
+    /// Creates new child subtree with `label` at index `i`.
+    /// An index past the last child is clamped, appending the subtree.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    ///
+    /// let mut tree = Tree::new(0);
+    /// tree.create_subtree(1);
+    /// tree.create_subtree(3);
+    ///
+    /// tree.insert_child_at(1, 2);
+    /// assert_eq!(tree.child_at(1).unwrap().label(), &2);
+    ///
+    /// // Clamped to the end.
+    /// tree.insert_child_at(10, 4);
+    /// assert_eq!(tree.child_at(3).unwrap().label(), &4);
+    /// ```
+    pub fn insert_child_at(&mut self, i: usize, label: T) {
+        self.insert_tree_at(i, Tree::new(label));
+    }
+
+    /// Inserts an existing `tree` as a child at index `i`.
+    /// An index past the last child is clamped, appending the subtree.
+    pub fn insert_tree_at(&mut self, i: usize, tree: Self) {
+        let i = i.min(self.children.len());
+        self.children.insert(i, tree);
+    }
+
+    /// Removes and returns the child subtree at index `i`.
+    /// Returns none if `i` is out of range.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    ///
+    /// let mut tree = Tree::new(0);
+    /// tree.create_subtree(1);
+    /// tree.create_subtree(2);
+    ///
+    /// let removed = tree.remove_child(0).unwrap();
+    /// assert_eq!(removed.label(), &1);
+    /// assert_eq!(tree.children(), 1);
+    /// assert_eq!(tree.child_at(0).unwrap().label(), &2);
+    ///
+    /// // Out of range.
+    /// assert!(tree.remove_child(1).is_none());
+    /// assert_eq!(tree.children(), 1);
+    /// ```
+    pub fn remove_child(&mut self, i: usize) -> Option<Self> {
+        if i < self.children.len() {
+            Some(self.children.remove(i))
+        } else {
+            None
+        }
+    }
+
+    /// Removes every subtree, below the root, whose label satisfies `f`.
+    /// Subtrees of removed subtrees are never visited.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    ///
+    /// let mut tree = Tree::new("todo");
+    /// tree.create_subtree("done");
+    /// tree.child_at_mut(0).unwrap().create_subtree("open");
+    /// tree.create_subtree("open");
+    /// tree.child_at_mut(1).unwrap().create_subtree("done");
+    ///
+    /// tree.prune(|label| *label == "done");
+    /// assert_eq!(tree.iter().collect::<Vec<_>>(), vec![&"todo", &"open"]);
+    /// ```
+    pub fn prune<F: FnMut(&T) -> bool>(&mut self, mut f: F) {
+        self.retain(|label| !f(label));
+    }
+
+    /// Retains, at every level below the root, only the subtrees whose label satisfies `f`.
+    /// Like `Vec::retain`, siblings are visited in order; subtrees of removed subtrees are
+    /// never visited.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    ///
+    /// let mut tree = Tree::new(0);
+    /// tree.create_subtree(1);
+    /// tree.create_subtree(2);
+    /// tree.child_at_mut(1).unwrap().create_subtree(3);
+    /// tree.child_at_mut(1).unwrap().create_subtree(4);
+    ///
+    /// tree.retain(|label| label % 2 == 0);
+    /// assert_eq!(tree.iter().collect::<Vec<_>>(), vec![&0, &2, &4]);
+    /// ```
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut f: F) {
+        self.retain_with(&mut f);
+    }
+
+    fn retain_with<F: FnMut(&T) -> bool>(&mut self, f: &mut F) {
+        self.children.retain(|c| f(&c.label));
+        for child in self.children.iter_mut() {
+            child.retain_with(f);
+        }
+    }
+
+    /// Swaps the child subtrees at indices `i` and `j`.
+    /// Returns false if either index is out of range.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    ///
+    /// let mut tree = Tree::new(0);
+    /// tree.create_subtree(1);
+    /// tree.create_subtree(2);
+    ///
+    /// assert!(tree.swap_children(0, 1));
+    /// assert_eq!(tree.child_at(0).unwrap().label(), &2);
+    /// assert!(!tree.swap_children(0, 2));
+    /// ```
+    pub fn swap_children(&mut self, i: usize, j: usize) -> bool {
+        if i < self.children.len() && j < self.children.len() {
+            self.children.swap(i, j);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Stably sorts the direct children by comparing their labels with `cmp`.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    ///
+    /// let mut tree = Tree::new("root");
+    /// tree.create_subtree("b");
+    /// tree.create_subtree("a");
+    /// tree.child_at_mut(0).unwrap().create_subtree("d");
+    /// tree.child_at_mut(0).unwrap().create_subtree("c");
+    ///
+    /// tree.sort_children_by(|a, b| a.cmp(b));
+    /// assert_eq!(tree.iter().collect::<Vec<_>>(), vec![&"root", &"a", &"b", &"d", &"c"]);
+    ///
+    /// tree.sort_all_by(|a, b| a.cmp(b));
+    /// assert_eq!(tree.iter().collect::<Vec<_>>(), vec![&"root", &"a", &"b", &"c", &"d"]);
+    /// ```
+    pub fn sort_children_by<F: FnMut(&T, &T) -> Ordering>(&mut self, mut cmp: F) {
+        self.children.sort_by(|a, b| cmp(&a.label, &b.label));
+    }
+
+    /// Stably sorts the children at every level by comparing their labels with `cmp`.
+    pub fn sort_all_by<F: FnMut(&T, &T) -> Ordering>(&mut self, mut cmp: F) {
+        self.sort_all_with(&mut cmp);
+    }
+
+    fn sort_all_with<F: FnMut(&T, &T) -> Ordering>(&mut self, cmp: &mut F) {
+        self.children.sort_by(|a, b| cmp(&a.label, &b.label));
+        for child in self.children.iter_mut() {
+            child.sort_all_with(cmp);
+        }
+    }
+
+    /// Moves the child subtree at index `from` so that it ends up at index `to`,
+    /// shifting the children in between.
+    /// Returns false if either index is out of range.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    ///
+    /// let mut tree = Tree::new(0);
+    /// tree.create_subtree(1);
+    /// tree.create_subtree(2);
+    /// tree.create_subtree(3);
+    ///
+    /// assert!(tree.move_child(0, 2));
+    /// let labels: Vec<_> = (0..3).map(|i| *tree.child_at(i).unwrap().label()).collect();
+    /// assert_eq!(labels, vec![2, 3, 1]);
+    /// assert!(!tree.move_child(3, 0));
+    /// ```
+    pub fn move_child(&mut self, from: usize, to: usize) -> bool {
+        if from < self.children.len() && to < self.children.len() {
+            let child = self.children.remove(from);
+            self.children.insert(to, child);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+impl<T: Ord> Tree<T> {
+    /// Returns whether both trees are equal once the children of every subtree are put in the same order.
+    /// Labels are compared, unlike with `isomorphic_to`, while the order of children is ignored.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    ///
+    /// let tree = Tree::<String>::from_sexpr("(a (b (c) (d)) (e))").unwrap();
+    /// assert!(tree.eq_unordered(&Tree::<String>::from_sexpr("(a (e) (b (d) (c)))").unwrap()));
+    /// assert!(!tree.eq_unordered(&Tree::<String>::from_sexpr("(a (e) (b (c)) (d))").unwrap()));
+    /// ```
+    pub fn eq_unordered(&self, other: &Self) -> bool {
+        // Numbers every distinct subtree of both trees by its label and the sorted numbers of its children.
+        let mut ids = BTreeMap::new();
+        self.canonical_id(&mut ids) == other.canonical_id(&mut ids)
+    }
+
+    fn canonical_id<'a>(&'a self, ids: &mut BTreeMap<(&'a T, Vec<usize>), usize>) -> usize {
+        let mut children: Vec<usize> = self.children.iter().map(|c| c.canonical_id(ids)).collect();
+        children.sort_unstable();
+        let next = ids.len();
+        *ids.entry((&self.label, children)).or_insert(next)
+    }
+}
+
+/// Drops the tree iteratively, so that even very deep trees can't overflow the stack.
+/// # Example
+/// ```
+/// use tt::tree::Tree;
+///
+/// let mut tree = Tree::new(0);
+/// for i in 1..1_000_000 {
+///     let mut parent = Tree::new(i);
+///     parent.attach_child(tree);
+///     tree = parent;
+/// }
+/// assert_eq!(tree.height(), 999_999);
+/// drop(tree);
+/// ```
+impl<T> Drop for Tree<T> {
+    fn drop(&mut self) {
+        let mut stack = mem::take(&mut self.children);
+        while let Some(mut tree) = stack.pop() {
+            stack.append(&mut tree.children);
+        }
+    }
+}
+
+/// Panics if the path doesn't point to an existing point on the tree,
+/// `Tree::get` being the fallible alternative.
+/// # Example
+/// ```
+/// use tt::tree::Tree;
+///
+/// let mut tree = Tree::new(0);
+/// tree.create_subtree(1);
+///
+/// let path = vec![0];
+/// assert_eq!(tree[&path].label(), &1);
+/// tree[&path].set_label(2);
+/// assert_eq!(tree[&path].label(), &2);
+/// ```
+/// ```should_panic
+/// use tt::tree::Tree;
+///
+/// let tree = Tree::new(0);
+/// let _ = &tree[&vec![0]];
+/// ```
+impl<T> Index<&Path> for Tree<T> {
+    type Output = Self;
+
+    fn index(&self, path: &Path) -> &Self::Output {
+        self.get(path).expect("path doesn't point to an existing point on the tree")
+    }
+}
+
+impl<T> IndexMut<&Path> for Tree<T> {
+    fn index_mut(&mut self, path: &Path) -> &mut Self::Output {
+        self.get_mut(path).expect("path doesn't point to an existing point on the tree")
+    }
+}
+
+/// Renders the tree like the Unix `tree` command, using a default `TreePrinter`.
+/// # Example
+/// ```
+/// use tt::tree::Tree;
+///
+/// let mut tree = Tree::new("root");
+/// tree.create_subtree("a");
+/// tree.child_at_mut(0).unwrap().create_subtree("b");
+/// tree.create_subtree("c");
+///
+/// assert_eq!(tree.to_string(), "root\n├── a\n│   └── b\n└── c");
+/// ```
+impl<T: fmt::Display> fmt::Display for Tree<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        TreePrinter::new().write(self, f)
     }
 }
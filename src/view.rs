@@ -0,0 +1,188 @@
+//! A collapsible, keyboard-driven view of a tree, for embedding a `Focus` in a terminal UI.
+//!
+//! `TreeView` keeps which subtrees are collapsed, lists the rows left visible and maps
+//! keys to jumps over them. It draws nothing itself: each `Row` displays as an indented,
+//! marked label, ready to be handed to whatever renders the terminal.
+//!
+//! There is no ratatui `Widget` for it, nor a mapping from crossterm's `KeyEvent` to `Key`:
+//! neither crate is a dependency, so frontends still turn their key events into `Key`s
+//! and draw the rows themselves.
+//! # Example
+//! ```
+//! use tt::tree::Tree;
+//! use tt::focus::Focus;
+//! use tt::view::{Key, TreeView};
+//!
+//! let mut tree = Tree::new("root");
+//! tree.create_subtree("a");
+//! tree.child_at_mut(0).unwrap().create_subtree("b");
+//! tree.create_subtree("c");
+//!
+//! let mut focus = Focus::from(tree, None).unwrap();
+//! let mut view = TreeView::new();
+//! assert!(view.handle_key(&mut focus, Key::Down));
+//! assert!(view.handle_key(&mut focus, Key::Left));
+//! assert!(view.is_collapsed(&[0]));
+//!
+//! let rows: Vec<_> = view.rows(focus.tree()).iter().map(ToString::to_string).collect();
+//! assert_eq!(rows, vec!["▾ root", "  ▸ a", "    c"]);
+//!
+//! assert!(view.handle_key(&mut focus, Key::Down));
+//! assert_eq!(focus.focused().label(), &"c");
+//! assert!(!view.handle_key(&mut focus, Key::Down));
+//! ```
+
+use alloc::collections::BTreeSet;
+use alloc::{vec, vec::Vec};
+use core::fmt;
+
+use super::focus::{Focus, Jump};
+use super::tree::{Path, Tree};
+
+/// A key a `TreeView` responds to.
+/// * `Key::Up` / `Key::Down` - To the previous / next visible row
+/// * `Key::Home` / `Key::End` - To the first / last visible row
+/// * `Key::Left` - Collapses the focused subtree, or goes to its parent if it's collapsed or a leaf
+/// * `Key::Right` - Expands the focused subtree, or goes to its first child if it's expanded
+/// * `Key::Toggle` - Collapses or expands the focused subtree
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Key {
+    Up,
+    Down,
+    Home,
+    End,
+    Left,
+    Right,
+    Toggle
+}
+
+/// The expand/collapse state of a view of a tree.
+/// Collapsed subtrees are kept by path, so edits moving subtrees around don't carry it along.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct TreeView {
+    collapsed: BTreeSet<Path>
+}
+
+/// A visible subtree of a `TreeView`.
+/// Displays as its label indented by its depth, marked with whether it's collapsed or expanded.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Row<'a, T> {
+    path: Path,
+    tree: &'a Tree<T>,
+    collapsed: bool
+}
+
+impl<'a, T> Row<'a, T> {
+    pub fn path(&self) -> &Path { &self.path }
+    /// Returns how deep the subtree is, the root having depth `0`.
+    pub fn depth(&self) -> usize { self.path.len() }
+    pub fn tree(&self) -> &'a Tree<T> { self.tree }
+    pub fn label(&self) -> &'a T { self.tree.label() }
+    /// Returns whether the subtree has children hidden by collapsing it.
+    pub fn is_collapsed(&self) -> bool { self.collapsed }
+}
+
+impl<'a, T: fmt::Display> fmt::Display for Row<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let marker = if self.tree.is_leaf() { ' ' } else if self.collapsed { '▸' } else { '▾' };
+        write!(f, "{:indent$}{} {}", "", marker, self.tree.label(), indent = 2 * self.depth())
+    }
+}
+
+impl TreeView {
+    /// Constructs and returns a new `TreeView` with every subtree expanded.
+    pub fn new() -> Self {
+        Self { collapsed: BTreeSet::new() }
+    }
+
+    /// Returns whether the subtree at `path` is collapsed.
+    pub fn is_collapsed(&self, path: &[usize]) -> bool { self.collapsed.contains(path) }
+
+    pub fn collapse(&mut self, path: Path) { self.collapsed.insert(path); }
+    pub fn expand(&mut self, path: &[usize]) { self.collapsed.remove(path); }
+
+    /// Collapses the subtree at `path` if it is expanded, and expands it otherwise.
+    pub fn toggle(&mut self, path: Path) {
+        if !self.collapsed.remove(&path) {
+            self.collapsed.insert(path);
+        }
+    }
+
+    /// Expands every subtree.
+    pub fn expand_all(&mut self) { self.collapsed.clear(); }
+
+    /// Returns the visible subtrees of `tree` in depth-first pre-order,
+    /// skipping the descendants of collapsed ones.
+    pub fn rows<'a, T>(&self, tree: &'a Tree<T>) -> Vec<Row<'a, T>> {
+        let mut rows = Vec::new();
+        let mut stack = vec![(Path::new(), tree)];
+        while let Some((path, tree)) = stack.pop() {
+            let collapsed = !tree.is_leaf() && self.is_collapsed(&path);
+            if !collapsed {
+                for (i, child) in tree.children_iter().enumerate().rev() {
+                    let mut path = path.clone();
+                    path.push(i);
+                    stack.push((path, child));
+                }
+            }
+            rows.push(Row { path, tree, collapsed });
+        }
+        rows
+    }
+
+    /// Returns the jump moving the focus of `focus` as `key` does, if `key` moves it.
+    /// The focus, when within a collapsed subtree, moves as if from the row of that subtree.
+    /// # Example
+    /// ```
+    /// use tt::focus::{Focus, Jump};
+    /// use tt::view::{Key, TreeView};
+    ///
+    /// let mut focus = Focus::new(0);
+    /// focus.create_subtree(1);
+    /// focus.create_subtree(2);
+    ///
+    /// let mut view = TreeView::new();
+    /// assert_eq!(view.jump_for(&focus, Key::Home), Some(Jump::ToPath(vec![])));
+    /// assert_eq!(view.jump_for(&focus, Key::Left), Some(Jump::Up));
+    /// assert_eq!(view.jump_for(&focus, Key::Right), None);
+    ///
+    /// // The focused subtree is hidden, moving from its parent.
+    /// view.collapse(vec![0]);
+    /// assert_eq!(view.jump_for(&focus, Key::Up), Some(Jump::ToPath(vec![])));
+    /// assert_eq!(view.jump_for(&focus, Key::Down), None);
+    /// ```
+    pub fn jump_for<T>(&self, focus: &Focus<T>, key: Key) -> Option<Jump> {
+        let rows = self.rows(focus.tree());
+        let current = rows.iter().rposition(|row| focus.path().starts_with(&row.path))?;
+        let row = &rows[current];
+        let to = match key {
+            Key::Up    => rows.get(current.checked_sub(1)?)?,
+            Key::Down  => rows.get(current + 1)?,
+            Key::Home  => rows.first()?,
+            Key::End   => rows.last()?,
+            Key::Left  if row.tree.is_leaf() || row.collapsed => return focus.parent().map(|_| Jump::Up),
+            Key::Right if !row.tree.is_leaf() && !row.collapsed => return Some(Jump::Down),
+            Key::Left | Key::Right | Key::Toggle => return None
+        };
+        Some(Jump::ToPath(to.path.clone()))
+    }
+
+    /// Responds to `key`, collapsing or expanding the focused subtree or moving the focus.
+    /// Returns false if `key` changed neither.
+    pub fn handle_key<T>(&mut self, focus: &mut Focus<T>, key: Key) -> bool {
+        let path = focus.path().clone();
+        let leaf = focus.focused().is_leaf();
+        match key {
+            Key::Left if !leaf && !self.is_collapsed(&path) => self.collapse(path),
+            Key::Right if !leaf && self.is_collapsed(&path) => self.expand(&path),
+            Key::Toggle if !leaf => self.toggle(path),
+            Key::Toggle => return false,
+            _ => return match self.jump_for(focus, key) {
+                Some(Jump::ToPath(to)) if &to == focus.path() => false,
+                Some(jump) => focus.jump(jump).is_ok(),
+                None => false
+            }
+        }
+        true
+    }
+}
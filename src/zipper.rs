@@ -0,0 +1,424 @@
+//! A zipper, holding the focused subtree by value along with the context it was taken out of,
+//! so that moving to the parent, a child or a sibling takes constant time whatever the depth.
+
+use alloc::vec::Vec;
+use core::mem;
+
+use super::focus::{Focus, Jump, JumpError, LateralPolicy, Path};
+use super::tree::Tree;
+
+/// The context of a focused subtree: the label of its parent and its siblings.
+/// To be taken out in constant time, the focused subtree was swapped with the last sibling,
+/// so the siblings are in order except for the last one, which sits at `index`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Crumb<T> {
+    label: T,
+    siblings: Vec<Tree<T>>,
+    index: usize
+}
+
+/// A tool for building and navigating trees in which movements take constant time.
+/// Unlike `Focus`, which keeps the whole tree and walks the path from the root,
+/// `Zipper` takes the focused subtree out of the tree, keeping the path back as context.
+///
+/// Moving up, down or laterally and editing the focused subtree take constant time,
+/// while inserting or deleting siblings takes time proportional to the amount of siblings.
+/// # Example
+/// ```
+/// use tt::focus::{Jump, Focus};
+/// use tt::zipper::Zipper;
+///
+/// let mut zipper = Zipper::new(0);
+/// zipper.create_subtree(1);
+/// zipper.jump(Jump::Up).unwrap();
+/// zipper.create_subtree(2);
+/// zipper.create_subtree(3);
+/// assert_eq!(zipper.path(), vec![1, 0]);
+///
+/// zipper.jump(Jump::Up).unwrap();
+/// zipper.jump(Jump::Lateral(-1)).unwrap();
+/// assert_eq!(zipper.focused().label(), &1);
+///
+/// let focus: Focus<_> = zipper.into();
+/// assert_eq!(focus.labels(), vec![&0, &1]);
+///
+/// let zipper: Zipper<_> = focus.into();
+/// assert_eq!(zipper.into_tree().iter().collect::<Vec<_>>(), vec![&0, &1, &2, &3]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Zipper<T> {
+    focus: Tree<T>,
+    crumbs: Vec<Crumb<T>>,
+    lateral: LateralPolicy
+}
+
+impl<T> Zipper<T> {
+    /// Constructs and returns a new `Zipper` from provided `Tree`.
+    /// * If `None` is provided as `path` - the zipper will be on the root.
+    /// * If `Some(path)` is provided as `path` - the zipper will be on the subtree at `path`.
+    ///
+    /// Returns none if the provided path doesn't point to an existing point on the tree.
+    pub fn from(tree: Tree<T>, path: Option<Path>) -> Option<Self> {
+        let path = path.unwrap_or_default();
+        if !tree.contains_path(&path) {
+            return None;
+        }
+
+        let mut zipper = Self { focus: tree, crumbs: Vec::new(), lateral: LateralPolicy::default() };
+        for &i in &path {
+            zipper.down(i);
+        }
+        Some(zipper)
+    }
+
+    /// Constructs and returns a new `Zipper` from provided `label`.
+    pub fn new(label: T) -> Self {
+        Self::from(Tree::new(label), None).unwrap()
+    }
+
+    /// Returns the path to the focused tree/subtree.
+    pub fn path(&self) -> Path {
+        self.crumbs.iter().map(|crumb| crumb.index).collect()
+    }
+
+    /// Returns the amount of ancestors of the focused subtree, the root having depth `0`.
+    pub fn depth(&self) -> usize { self.crumbs.len() }
+
+    /// Returns how lateral jumps past the first or last sibling behave.
+    pub fn lateral_policy(&self) -> LateralPolicy { self.lateral }
+
+    /// Sets how lateral jumps past the first or last sibling behave.
+    pub fn set_lateral_policy(&mut self, policy: LateralPolicy) {
+        self.lateral = policy;
+    }
+
+    /// Returns the currently focused tree/subtree.
+    pub fn focused(&self) -> &Tree<T> { &self.focus }
+
+    /// Returns a mutable reference to the currently focused tree/subtree.
+    pub fn focused_mut(&mut self) -> &mut Tree<T> { &mut self.focus }
+
+    /// Consumes the zipper and returns the whole tree, from its root.
+    pub fn into_tree(mut self) -> Tree<T> {
+        while self.up() {}
+        self.focus
+    }
+
+    /// Changes focus according to the provided `jump`.
+    /// A lateral jump past the first or last sibling behaves according to the `LateralPolicy`.
+    /// `Jump::Root`, `Jump::ToPath` and the jumps in depth-first pre-order take time
+    /// proportional to the distance travelled.
+    ///
+    /// Returns an error, leaving the focus unchanged, if the jump is impossible.
+    /// # Example
+    /// ```
+    /// use tt::tree::Tree;
+    /// use tt::focus::{Jump, JumpError};
+    /// use tt::zipper::Zipper;
+    ///
+    /// let mut tree = Tree::new(0);
+    /// tree.create_subtree(1);
+    /// tree.child_at_mut(0).unwrap().create_subtree(2);
+    /// tree.create_subtree(3);
+    ///
+    /// let mut zipper = Zipper::from(tree, None).unwrap();
+    /// let mut labels = vec![*zipper.focused().label()];
+    /// while zipper.jump(Jump::Next).is_ok() {
+    ///     labels.push(*zipper.focused().label());
+    /// }
+    /// assert_eq!(labels, vec![0, 1, 2, 3]);
+    ///
+    /// zipper.jump(Jump::PrevLeaf).unwrap();
+    /// assert_eq!(zipper.path(), vec![0, 0]);
+    /// assert_eq!(zipper.jump(Jump::PrevLeaf), Err(JumpError::AtStart));
+    /// assert_eq!(zipper.jump(Jump::ToPath(vec![1, 0])), Err(JumpError::InvalidPath));
+    /// assert_eq!(zipper.path(), vec![0, 0]);
+    /// ```
+    pub fn jump(&mut self, jump: Jump) -> Result<(), JumpError> {
+        match jump {
+            Jump::Up => if !self.up() { return Err(JumpError::AtRoot) },
+            Jump::Down if self.focus.is_leaf() => return Err(JumpError::AtLeaf),
+            Jump::Down => self.down(0),
+            Jump::DownLast if self.focus.is_leaf() => return Err(JumpError::AtLeaf),
+            Jump::DownLast => self.down(self.focus.children() - 1),
+            Jump::Child(_) if self.focus.is_leaf() => return Err(JumpError::AtLeaf),
+            Jump::Child(i) if i < self.focus.children() => self.down(i),
+            Jump::Child(_) => return Err(JumpError::InvalidPath),
+            Jump::Lateral(_) | Jump::FirstSibling | Jump::LastSibling if self.crumbs.is_empty() => {
+                return Err(JumpError::AtRoot)
+            }
+            Jump::Lateral(x) => {
+                let o = self.index();
+                match self.lateral.apply(o, x, self.siblings()) {
+                    Some(n) if n != o || x == 0 => self.switch(n),
+                    _ => return Err(JumpError::NoSibling)
+                }
+            }
+            Jump::FirstSibling => self.switch(0),
+            Jump::LastSibling => self.switch(self.siblings() - 1),
+            Jump::Root => while self.up() {},
+            Jump::ToPath(path) => {
+                let from = self.path();
+                if !self.go(&path) {
+                    self.go(&from);
+                    return Err(JumpError::InvalidPath);
+                }
+            }
+            Jump::Next => if !self.next() { return Err(JumpError::AtEnd) },
+            Jump::Prev => if !self.prev() { return Err(JumpError::AtStart) },
+            Jump::NextLeaf => {
+                if !self.next() {
+                    return Err(JumpError::AtEnd);
+                }
+                while !self.focus.is_leaf() {
+                    self.next();
+                }
+            }
+            Jump::PrevLeaf => {
+                // A previous leaf exists exactly if some ancestor-or-self has a previous sibling.
+                if self.crumbs.iter().all(|crumb| crumb.index == 0) {
+                    return Err(JumpError::AtStart);
+                }
+                self.prev();
+                while !self.focus.is_leaf() {
+                    self.prev();
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Changes focus according to the provided `jump`, ignoring impossible jumps.
+    pub fn jump_silent(&mut self, jump: Jump) {
+        let _ = self.jump(jump);
+    }
+
+    /// Returns the index of the focused subtree among its siblings.
+    fn index(&self) -> usize {
+        self.crumbs.last().map_or(0, |crumb| crumb.index)
+    }
+
+    /// Returns the amount of siblings of the focused subtree, including itself.
+    fn siblings(&self) -> usize {
+        self.crumbs.last().map_or(1, |crumb| crumb.siblings.len() + 1)
+    }
+
+    /// Moves the focus to its child at index `i`.
+    fn down(&mut self, i: usize) {
+        let mut siblings = mem::take(self.focus.split_mut().1);
+        let child = siblings.swap_remove(i);
+        let (label, _) = mem::replace(&mut self.focus, child).into_parts();
+        self.crumbs.push(Crumb { label, siblings, index: i });
+    }
+
+    /// Moves the focus to its parent.
+    /// Returns false if the root is focused.
+    fn up(&mut self) -> bool {
+        let crumb = match self.crumbs.pop() {
+            Some(crumb) => crumb,
+            None => return false
+        };
+
+        let child = mem::replace(&mut self.focus, Tree::new(crumb.label));
+        *self.focus.split_mut().1 = plug(crumb.siblings, crumb.index, child);
+        true
+    }
+
+    /// Moves the focus to its sibling at index `j`.
+    fn switch(&mut self, j: usize) {
+        let crumb = self.crumbs.last_mut().unwrap();
+        let (i, last) = (crumb.index, crumb.siblings.len());
+        if i == j {
+            return;
+        }
+
+        // The last sibling sits where the focus was taken out.
+        mem::swap(&mut self.focus, &mut crumb.siblings[if j == last { i } else { j }]);
+        if i != last && j != last {
+            crumb.siblings.swap(i, j);
+        }
+        crumb.index = j;
+    }
+
+    /// Moves the focus from the root to the subtree at `path`.
+    /// Returns false, leaving the focus somewhere along `path`, if it doesn't point to an existing point on the tree.
+    fn go(&mut self, path: &[usize]) -> bool {
+        while self.up() {}
+        for &i in path {
+            if i >= self.focus.children() {
+                return false;
+            }
+            self.down(i);
+        }
+        true
+    }
+
+    /// Moves the focus to the next subtree in depth-first pre-order.
+    /// Returns false, leaving the focus unchanged, if there is none.
+    fn next(&mut self) -> bool {
+        if !self.focus.is_leaf() {
+            self.down(0);
+            return true;
+        }
+
+        match self.crumbs.iter().rposition(|crumb| crumb.index < crumb.siblings.len()) {
+            Some(k) => {
+                while self.crumbs.len() > k + 1 {
+                    self.up();
+                }
+                let j = self.index() + 1;
+                self.switch(j);
+                true
+            }
+            None => false
+        }
+    }
+
+    /// Moves the focus to the previous subtree in depth-first pre-order.
+    /// Returns false, leaving the focus unchanged, if there is none.
+    fn prev(&mut self) -> bool {
+        match self.crumbs.last().map(|crumb| crumb.index) {
+            Some(0) => self.up(),
+            Some(i) => {
+                self.switch(i - 1);
+                while !self.focus.is_leaf() {
+                    self.down(self.focus.children() - 1);
+                }
+                true
+            }
+            None => false
+        }
+    }
+
+    /// Replaces the label of the focused subtree, returning the old one.
+    pub fn set_label(&mut self, label: T) -> T {
+        self.focus.set_label(label)
+    }
+
+    /// Replaces the focused subtree with `tree`, returning the old one.
+    pub fn replace_focused(&mut self, tree: Tree<T>) -> Tree<T> {
+        mem::replace(&mut self.focus, tree)
+    }
+
+    /// Creates new child subtree of focused tree/subtree, moving the focus into it.
+    pub fn create_subtree(&mut self, label: T) {
+        self.graft(Tree::new(label));
+    }
+
+    /// Attaches `tree` as the last child of focused tree/subtree, moving the focus into it.
+    pub fn graft(&mut self, tree: Tree<T>) {
+        let i = self.focus.children();
+        self.focus.attach_child(tree);
+        self.down(i);
+    }
+
+    /// Attaches `tree` as a child of focused tree/subtree at index `i`, moving the focus into it.
+    /// An index past the last child is clamped, appending the tree.
+    pub fn graft_at(&mut self, i: usize, tree: Tree<T>) {
+        let i = i.min(self.focus.children());
+        self.focus.insert_tree_at(i, tree);
+        self.down(i);
+    }
+
+    /// Creates new sibling subtree with `label` right before the focused subtree, moving the focus to it.
+    /// Returns false, leaving the tree unchanged, if the root is focused.
+    pub fn insert_sibling_before(&mut self, label: T) -> bool {
+        let i = self.index();
+        self.insert_sibling_at(i, Tree::new(label))
+    }
+
+    /// Creates new sibling subtree with `label` right after the focused subtree, moving the focus to it.
+    /// Returns false, leaving the tree unchanged, if the root is focused.
+    pub fn insert_sibling_after(&mut self, label: T) -> bool {
+        let i = self.index();
+        self.insert_sibling_at(i + 1, Tree::new(label))
+    }
+
+    fn insert_sibling_at(&mut self, i: usize, tree: Tree<T>) -> bool {
+        if !self.up() {
+            return false;
+        }
+        self.graft_at(i, tree);
+        true
+    }
+
+    /// Removes and returns the focused subtree, moving the focus to its previous sibling,
+    /// or to its parent if it was the first child.
+    /// Returns none if the root is focused.
+    /// # Example
+    /// ```
+    /// use tt::zipper::Zipper;
+    ///
+    /// let mut zipper = Zipper::new(0);
+    /// assert!(zipper.delete_focused().is_none());
+    ///
+    /// zipper.create_subtree(1);
+    /// zipper.insert_sibling_after(2);
+    /// zipper.insert_sibling_after(3);
+    /// assert_eq!(zipper.delete_focused().unwrap().label(), &3);
+    /// assert_eq!(zipper.focused().label(), &2);
+    /// assert_eq!(zipper.detach().unwrap().label(), &2);
+    /// assert_eq!(zipper.into_tree().iter().collect::<Vec<_>>(), vec![&0, &1]);
+    /// ```
+    pub fn delete_focused(&mut self) -> Option<Tree<T>> {
+        match self.index() {
+            0 => self.detach(),
+            i => {
+                self.switch(i - 1);
+                let crumb = self.crumbs.last_mut().unwrap();
+                // The removed sibling is the one right after the focus, in place of it if it was last.
+                let at = if i == crumb.siblings.len() { i - 1 } else { i };
+                Some(crumb.siblings.remove(at))
+            }
+        }
+    }
+
+    /// Removes and returns the focused subtree, moving the focus to its parent.
+    /// Returns none if the root is focused.
+    pub fn detach(&mut self) -> Option<Tree<T>> {
+        let crumb = self.crumbs.pop()?;
+        let mut siblings = crumb.siblings;
+        if crumb.index < siblings.len() {
+            let last = siblings.remove(crumb.index);
+            siblings.push(last);
+        }
+
+        let mut parent = Tree::new(crumb.label);
+        *parent.split_mut().1 = siblings;
+        Some(mem::replace(&mut self.focus, parent))
+    }
+
+    /// Get all labels along current path.
+    pub fn labels(&self) -> Vec<&T> {
+        self.crumbs.iter().map(|crumb| &crumb.label).chain(Some(self.focus.label())).collect()
+    }
+}
+
+/// Puts `tree` back among `siblings` at index `i`, where it was taken out, returning them in order.
+fn plug<T>(mut siblings: Vec<Tree<T>>, i: usize, tree: Tree<T>) -> Vec<Tree<T>> {
+    siblings.push(tree);
+    let last = siblings.len() - 1;
+    siblings.swap(i, last);
+    siblings
+}
+
+impl<T> From<Focus<T>> for Zipper<T> {
+    /// Converts a focus into a zipper on the same path.
+    /// Marks, history, the journal and the clipboard of the focus are dropped.
+    fn from(focus: Focus<T>) -> Self {
+        let (path, lateral) = (focus.path().clone(), focus.lateral_policy());
+        let mut zipper = Zipper::from(focus.into_tree(), Some(path)).unwrap();
+        zipper.lateral = lateral;
+        zipper
+    }
+}
+
+impl<T> From<Zipper<T>> for Focus<T> {
+    /// Converts a zipper into a focus on the same path.
+    fn from(zipper: Zipper<T>) -> Self {
+        let (path, lateral) = (zipper.path(), zipper.lateral);
+        let mut focus = Focus::from(zipper.into_tree(), Some(path)).unwrap();
+        focus.set_lateral_policy(lateral);
+        focus
+    }
+}